@@ -2,16 +2,19 @@
 //!
 //! Should only be used for benchmarking as it may break in other contexts.
 
-use crate::service::FullClient;
+use crate::{service::FullClient, tictactoe};
 
+use codec::Decode;
+use frame_support::traits::Get;
 use node_template_runtime as runtime;
-use runtime::{AccountId, Balance, BalancesCall, SystemCall};
-use sc_cli::Result;
-use sc_client_api::BlockBackend;
-use sp_core::{Encode, Pair};
+use pallet_tictactoe::{Game, GameStatus};
+use runtime::{AccountId, Balance, BalancesCall, SystemCall, TictactoeCall, EXISTENTIAL_DEPOSIT};
+use sc_cli::{Result, RuntimeVersion};
+use sc_client_api::{BlockBackend, StorageProvider};
+use sp_core::{storage::StorageKey, Encode, Pair};
 use sp_inherents::{InherentData, InherentDataProvider};
 use sp_keyring::Sr25519Keyring;
-use sp_runtime::{OpaqueExtrinsic, SaturatedConversion};
+use sp_runtime::{generic::Era, OpaqueExtrinsic, SaturatedConversion};
 
 use std::{sync::Arc, time::Duration};
 
@@ -95,6 +98,140 @@ impl frame_benchmarking_cli::ExtrinsicBuilder for TransferKeepAliveBuilder {
 	}
 }
 
+/// Generates `Tictactoe::create_game` extrinsics for the benchmarks.
+///
+/// Games are created by Bob with the minimum bet allowed. The block must not take the open games
+/// over `MaxOpenGames`, or the calls would fail.
+///
+/// Note: Should only be used for benchmarking.
+pub struct CreateGameBuilder {
+	client: Arc<FullClient>,
+	bet: Balance,
+	open_games: u32,
+}
+
+impl CreateGameBuilder {
+	/// Creates a new [`Self`] from the given client.
+	pub fn new(client: Arc<FullClient>) -> Self {
+		let bet = min_bet(client.as_ref());
+		let open_games = tictactoe_value(client.as_ref(), b"OpenGames").unwrap_or_default();
+		Self { client, bet, open_games }
+	}
+}
+
+impl frame_benchmarking_cli::ExtrinsicBuilder for CreateGameBuilder {
+	fn pallet(&self) -> &str {
+		"tictactoe"
+	}
+
+	fn extrinsic(&self) -> &str {
+		"create_game"
+	}
+
+	fn build(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str> {
+		if self.open_games.saturating_add(nonce) >= MaxOpenGames::get() {
+			return Err("The block takes the open games over `MaxOpenGames`")
+		}
+		let acc = Sr25519Keyring::Bob.pair();
+		let extrinsic: OpaqueExtrinsic =
+			create_benchmark_extrinsic(self.client.as_ref(), acc, create_game(self.bet), nonce)
+				.into();
+
+		Ok(extrinsic)
+	}
+}
+
+/// Generates `Tictactoe::join_game` extrinsics for the benchmarks.
+///
+/// The block is executed on top of the best block, so the games to join must already be open
+/// there. Bob joins the open games seeded at genesis of the `tictactoe-bench` chain, from the
+/// first one onwards.
+///
+/// Note: Should only be used for benchmarking.
+pub struct JoinGameBuilder {
+	client: Arc<FullClient>,
+	first_game: u32,
+}
+
+impl JoinGameBuilder {
+	/// Creates a new [`Self`] from the given client.
+	pub fn new(client: Arc<FullClient>) -> Self {
+		// Seeded games are the last created ones and none of them has ended.
+		let game_index: u32 = tictactoe_value(client.as_ref(), b"GameIndex").unwrap_or_default();
+		let open_games: u32 = tictactoe_value(client.as_ref(), b"OpenGames").unwrap_or_default();
+		Self { client, first_game: game_index.saturating_sub(open_games) }
+	}
+}
+
+impl frame_benchmarking_cli::ExtrinsicBuilder for JoinGameBuilder {
+	fn pallet(&self) -> &str {
+		"tictactoe"
+	}
+
+	fn extrinsic(&self) -> &str {
+		"join_game"
+	}
+
+	fn build(&self, nonce: u32) -> std::result::Result<OpaqueExtrinsic, &'static str> {
+		let game_index = self.first_game.saturating_add(nonce);
+		let joinable = tictactoe_game(self.client.as_ref(), game_index).map_or(false, |game| {
+			game.status() == GameStatus::Open &&
+				game.payout_addresses().1.is_none() &&
+				game.tournament().is_none()
+		});
+		if !joinable {
+			return Err("Not enough open games to join, benchmark on the `tictactoe-bench` chain")
+		}
+		let acc = Sr25519Keyring::Bob.pair();
+		let extrinsic: OpaqueExtrinsic = create_benchmark_extrinsic(
+			self.client.as_ref(),
+			acc,
+			TictactoeCall::join_game { game_index }.into(),
+			nonce,
+		)
+		.into();
+
+		Ok(extrinsic)
+	}
+}
+
+/// Maximum number of games that haven't ended in the runtime.
+type MaxOpenGames = <runtime::Runtime as pallet_tictactoe::Config>::MaxOpenGames;
+
+/// Classic single round game with the given bet.
+fn create_game(bet: Balance) -> runtime::RuntimeCall {
+	TictactoeCall::create_game {
+		bet,
+		series_length: 1,
+		board_size: 3,
+		win_length: 3,
+		join_bonus: None,
+	}
+	.into()
+}
+
+/// Minimum bet allowed by the tictactoe pallet in the best block.
+fn min_bet(client: &FullClient) -> Balance {
+	tictactoe_value::<(Balance, Option<Balance>)>(client, b"BetLimits")
+		.map_or(EXISTENTIAL_DEPOSIT, |(min_bet, _)| min_bet.max(EXISTENTIAL_DEPOSIT))
+}
+
+/// Read a storage value of the tictactoe pallet in the best block.
+fn tictactoe_value<T: Decode>(client: &FullClient, item: &[u8]) -> Option<T> {
+	best_block_value(client, tictactoe::storage_prefix(tictactoe::PALLET, item))
+}
+
+/// Read a game of the tictactoe pallet in the best block.
+fn tictactoe_game(client: &FullClient, game_index: u32) -> Option<Game<Balance, AccountId>> {
+	best_block_value(client, tictactoe::map_key(b"Games", game_index))
+}
+
+/// Read and decode the value of a storage key in the best block.
+fn best_block_value<T: Decode>(client: &FullClient, key: Vec<u8>) -> Option<T> {
+	let data = client.storage(client.chain_info().best_hash, &StorageKey(key)).ok().flatten()?;
+	T::decode(&mut &data.0[..]).ok()
+}
+
 /// Create a transaction using the given `call`.
 ///
 /// Note: Should only be used for benchmarking.
@@ -112,41 +249,53 @@ pub fn create_benchmark_extrinsic(
 		.checked_next_power_of_two()
 		.map(|c| c / 2)
 		.unwrap_or(2) as u64;
+	sign_extrinsic(
+		&sender,
+		call,
+		nonce,
+		Era::mortal(period, best_block.saturated_into()),
+		(genesis_hash, best_hash),
+		&runtime::VERSION,
+	)
+}
+
+/// Sign a transaction of `call` mortal for `era`, with the hashes of the genesis block and of the
+/// block the era starts at.
+///
+/// Note: Should only be used for benchmarking and load testing.
+pub fn sign_extrinsic(
+	sender: &sp_core::sr25519::Pair,
+	call: runtime::RuntimeCall,
+	nonce: u32,
+	era: Era,
+	(genesis_hash, era_hash): (runtime::Hash, runtime::Hash),
+	version: &RuntimeVersion,
+) -> runtime::UncheckedExtrinsic {
 	let extra: runtime::SignedExtra = (
 		frame_system::CheckNonZeroSender::<runtime::Runtime>::new(),
 		frame_system::CheckSpecVersion::<runtime::Runtime>::new(),
 		frame_system::CheckTxVersion::<runtime::Runtime>::new(),
 		frame_system::CheckGenesis::<runtime::Runtime>::new(),
-		frame_system::CheckEra::<runtime::Runtime>::from(sp_runtime::generic::Era::mortal(
-			period,
-			best_block.saturated_into(),
-		)),
+		frame_system::CheckEra::<runtime::Runtime>::from(era),
 		frame_system::CheckNonce::<runtime::Runtime>::from(nonce),
 		frame_system::CheckWeight::<runtime::Runtime>::new(),
-		pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
+		runtime::pallet_tictactoe::CheckTictactoeMove::<runtime::Runtime, _>::from(
+			pallet_transaction_payment::ChargeTransactionPayment::<runtime::Runtime>::from(0),
+		),
 	);
 
 	let raw_payload = runtime::SignedPayload::from_raw(
 		call.clone(),
 		extra.clone(),
-		(
-			(),
-			runtime::VERSION.spec_version,
-			runtime::VERSION.transaction_version,
-			genesis_hash,
-			best_hash,
-			(),
-			(),
-			(),
-		),
+		((), version.spec_version, version.transaction_version, genesis_hash, era_hash, (), (), ()),
 	);
 	let signature = raw_payload.using_encoded(|e| sender.sign(e));
 
 	runtime::UncheckedExtrinsic::new_signed(
-		call.clone(),
+		call,
 		sp_runtime::AccountId32::from(sender.public()).into(),
-		runtime::Signature::Sr25519(signature.clone()),
-		extra.clone(),
+		runtime::Signature::Sr25519(signature),
+		extra,
 	)
 }
 
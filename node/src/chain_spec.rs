@@ -1,16 +1,26 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	AccountId, AuraConfig, Balance, BalancesConfig, GenesisConfig, GrandpaConfig, Signature,
+	SudoConfig, SystemConfig, TictactoeConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
+use serde::Deserialize;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_consensus_grandpa::AuthorityId as GrandpaId;
-use sp_core::{sr25519, Pair, Public};
+use sp_core::{crypto::Ss58Codec, sr25519, Pair, Public};
 use sp_runtime::traits::{IdentifyAccount, Verify};
+use std::path::Path;
 
 // The URL for the telemetry server.
 // const STAGING_TELEMETRY_URL: &str = "wss://telemetry.polkadot.io/submit/";
 
+/// Bet of the demo games created at genesis, safeguard deposit, fee and bet limits are set
+/// relative to it.
+const DEMO_BET: Balance = 1_000_000_000_000;
+
+/// Open games hosted by Alice at genesis of the benchmark chain. More than the `join_game` calls
+/// that fit in a block, and below `MaxOpenGames`.
+const BENCHMARK_OPEN_GAMES: usize = 5_000;
+
 /// Specialized `ChainSpec`. This is a specialization of the general Substrate ChainSpec type.
 pub type ChainSpec = sc_service::GenericChainSpec<GenesisConfig>;
 
@@ -59,6 +69,57 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Demo games
+				tictactoe_config(vec![
+					(
+						get_account_id_from_seed::<sr25519::Public>("Alice"),
+						Some(get_account_id_from_seed::<sr25519::Public>("Bob")),
+						DEMO_BET,
+					),
+					(get_account_id_from_seed::<sr25519::Public>("Bob"), None, DEMO_BET),
+				]),
+				true,
+			)
+		},
+		// Bootnodes
+		vec![],
+		// Telemetry
+		None,
+		// Protocol ID
+		None,
+		None,
+		// Properties
+		None,
+		// Extensions
+		None,
+	))
+}
+
+/// Development chain where Alice hosts `BENCHMARK_OPEN_GAMES` open games at the minimum bet, for
+/// Bob to join them in `benchmark extrinsic`.
+pub fn benchmark_config() -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+
+	Ok(ChainSpec::from_genesis(
+		// Name
+		"Tic-Tac-Toe Benchmark",
+		// ID
+		"tictactoe_bench",
+		ChainType::Development,
+		move || {
+			let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+			let bob = get_account_id_from_seed::<sr25519::Public>("Bob");
+			let open_games = vec![(alice.clone(), None, DEMO_BET / 100); BENCHMARK_OPEN_GAMES];
+			testnet_genesis(
+				wasm_binary,
+				// Initial PoA authorities
+				vec![authority_keys_from_seed("Alice")],
+				// Sudo account
+				alice.clone(),
+				// Pre-funded accounts
+				vec![alice, bob],
+				// Open games
+				tictactoe_config(open_games),
 				true,
 			)
 		},
@@ -107,6 +168,20 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Demo games
+				tictactoe_config(vec![
+					(
+						get_account_id_from_seed::<sr25519::Public>("Alice"),
+						Some(get_account_id_from_seed::<sr25519::Public>("Bob")),
+						DEMO_BET,
+					),
+					(
+						get_account_id_from_seed::<sr25519::Public>("Charlie"),
+						Some(get_account_id_from_seed::<sr25519::Public>("Dave")),
+						DEMO_BET,
+					),
+					(get_account_id_from_seed::<sr25519::Public>("Eve"), None, DEMO_BET),
+				]),
 				true,
 			)
 		},
@@ -124,12 +199,181 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 	))
 }
 
+/// Scenario of the `tictactoe-demo` chain. Fields missing from an override file keep their
+/// default value.
+#[derive(Debug, Deserialize)]
+#[serde(default, rename_all = "camelCase", deny_unknown_fields)]
+pub struct DemoScenario {
+	/// Endowed players, as SS58 addresses or development seeds such as `Alice`.
+	pub players: Vec<String>,
+	/// Initial safeguard deposit.
+	pub safeguard_deposit: Balance,
+	/// Initial game fee.
+	pub game_fee: Balance,
+	/// Initial minimum bet.
+	pub min_bet: Balance,
+	/// Initial maximum bet, if any.
+	pub max_bet: Option<Balance>,
+	/// Games created at genesis, in index order.
+	pub games: Vec<DemoGame>,
+}
+
+/// Game created at genesis on the `tictactoe-demo` chain.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DemoGame {
+	/// Host of the game, one of the players.
+	pub host: String,
+	/// Joiner of the game, one of the players. The game is left open if not set.
+	pub joiner: Option<String>,
+	/// Bet of each player.
+	pub bet: Balance,
+	/// Each player proposes itself as winner, sending the joined game to mediation.
+	#[serde(default)]
+	pub disputed: bool,
+}
+
+impl Default for DemoScenario {
+	fn default() -> Self {
+		let game = |host: &str, joiner: Option<&str>, bet, disputed| DemoGame {
+			host: host.into(),
+			joiner: joiner.map(Into::into),
+			bet,
+			disputed,
+		};
+		Self {
+			players: ["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"]
+				.map(String::from)
+				.to_vec(),
+			safeguard_deposit: DEMO_BET / 10,
+			game_fee: DEMO_BET / 100,
+			min_bet: DEMO_BET / 100,
+			max_bet: Some(DEMO_BET * 1_000),
+			games: vec![
+				game("Alice", None, DEMO_BET, false),
+				game("Bob", Some("Charlie"), DEMO_BET, false),
+				game("Dave", Some("Eve"), DEMO_BET, true),
+				game("Ferdie", None, DEMO_BET * 5, false),
+			],
+		}
+	}
+}
+
+impl DemoScenario {
+	/// Read the scenario from a JSON override file, the default scenario if no file is passed.
+	pub fn load(path: Option<&Path>) -> Result<Self, String> {
+		let path = match path {
+			Some(path) => path,
+			None => return Ok(Self::default()),
+		};
+		let file = std::fs::read(path)
+			.map_err(|e| format!("Failed to read demo scenario {}: {}", path.display(), e))?;
+		serde_json::from_slice(&file)
+			.map_err(|e| format!("Invalid demo scenario {}: {}", path.display(), e))
+	}
+}
+
+/// Returns the account of a player, given as an SS58 address or a development seed.
+fn player_account(player: &str) -> AccountId {
+	AccountId::from_ss58check(player)
+		.unwrap_or_else(|_| get_account_id_from_seed::<sr25519::Public>(player))
+}
+
+/// Chain with games in every state, for front-end development. Alice is the authority and the
+/// sudo account, and is endowed along with the players of the scenario.
+pub fn demo_config(scenario: DemoScenario) -> Result<ChainSpec, String> {
+	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let mut endowed_accounts = vec![alice.clone()];
+	for player in scenario.players.iter().map(|player| player_account(player)) {
+		if !endowed_accounts.contains(&player) {
+			endowed_accounts.push(player);
+		}
+	}
+
+	let mut demo_games = Vec::new();
+	let mut disputed_demo_games = Vec::new();
+	for (game_index, game) in scenario.games.iter().enumerate() {
+		let player = |player: &String| {
+			scenario
+				.players
+				.contains(player)
+				.then(|| player_account(player))
+				.ok_or_else(|| format!("Demo game {} player {} is not listed", game_index, player))
+		};
+		let joiner = game.joiner.as_ref().map(player).transpose()?;
+		if game.disputed {
+			if joiner.is_none() {
+				return Err(format!("Disputed demo game {} must have a joiner", game_index))
+			}
+			disputed_demo_games.push(game_index as u32);
+		}
+		demo_games.push((player(&game.host)?, joiner, game.bet));
+	}
+	let bet_limits = (scenario.min_bet, scenario.max_bet);
+	if bet_limits.1.map_or(false, |max_bet| max_bet < bet_limits.0) {
+		return Err("Demo scenario minimum bet is greater than the maximum bet".into())
+	}
+
+	Ok(ChainSpec::from_genesis(
+		// Name
+		"Tic-Tac-Toe Demo",
+		// ID
+		"tictactoe_demo",
+		ChainType::Development,
+		move || {
+			testnet_genesis(
+				wasm_binary,
+				// Initial PoA authorities
+				vec![authority_keys_from_seed("Alice")],
+				// Sudo account
+				alice.clone(),
+				// Pre-funded accounts
+				endowed_accounts.clone(),
+				TictactoeConfig {
+					safeguard_deposit: scenario.safeguard_deposit,
+					game_fee: scenario.game_fee,
+					bet_limits,
+					demo_games: demo_games.clone(),
+					disputed_demo_games: disputed_demo_games.clone(),
+				},
+				true,
+			)
+		},
+		// Bootnodes
+		vec![],
+		// Telemetry
+		None,
+		// Protocol ID
+		None,
+		None,
+		// Properties
+		None,
+		// Extensions
+		None,
+	))
+}
+
+/// Tictactoe genesis config of the development and local testnet chains.
+fn tictactoe_config(demo_games: Vec<(AccountId, Option<AccountId>, Balance)>) -> TictactoeConfig {
+	TictactoeConfig {
+		safeguard_deposit: DEMO_BET / 10,
+		game_fee: DEMO_BET / 100,
+		bet_limits: (DEMO_BET / 100, Some(DEMO_BET * 1_000)),
+		// Hosts and joiners must be endowed.
+		demo_games,
+		disputed_demo_games: Vec::new(),
+	}
+}
+
 /// Configure initial storage state for FRAME modules.
 fn testnet_genesis(
 	wasm_binary: &[u8],
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	tictactoe: TictactoeConfig,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -152,5 +396,6 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		tictactoe,
 	}
 }
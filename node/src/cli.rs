@@ -1,4 +1,5 @@
 use sc_cli::RunCmd;
+use std::path::PathBuf;
 
 #[derive(Debug, clap::Parser)]
 pub struct Cli {
@@ -7,6 +8,15 @@ pub struct Cli {
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	/// Index tictactoe games in a local database and serve them through the `tictactoeIndex`
+	/// RPC methods. Requires `--state-pruning archive`.
+	#[arg(long)]
+	pub tictactoe_indexer: bool,
+
+	/// JSON file overriding the scenario of the `tictactoe-demo` chain.
+	#[arg(long, value_name = "PATH", global = true)]
+	pub demo_scenario: Option<PathBuf>,
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -50,4 +60,10 @@ pub enum Subcommand {
 
 	/// Db meta columns information.
 	ChainInfo(sc_cli::ChainInfoCmd),
+
+	/// Inspect the state of the tictactoe pallet in the local database.
+	Tictactoe(crate::tictactoe::TictactoeCmd),
+
+	/// Play games between dev accounts against a local node to load test it.
+	LoadTest(crate::load_test::LoadTestCmd),
 }
@@ -0,0 +1,459 @@
+//! Off-chain index of the tictactoe games played on the best chain, searchable through the
+//! `tictactoeIndex` RPC methods.
+//!
+//! Every block of the best chain is recorded in a local SQLite database along with the games,
+//! joins and results decoded from its events. Rows are deleted with the block that emitted them,
+//! so blocks retracted by a reorg are reverted by deleting them. Non-finalized blocks are reverted
+//! when the index is opened, as their fork may have been abandoned while the node was down.
+//!
+//! Events are read from the state of each block, so the node must keep the state of every block
+//! with `--state-pruning archive`. Each block is committed on its own, a block that fails to index
+//! is retried from the last indexed block on the next import.
+
+use codec::Decode;
+use futures::StreamExt;
+use node_template_runtime::{opaque::Block, BlockNumber, Hash, RuntimeEvent};
+use pallet_tictactoe::Event;
+use rusqlite::{params, Connection, OptionalExtension, Row};
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use serde::{Deserialize, Serialize};
+use sp_blockchain::{HeaderBackend, HeaderMetadata};
+use sp_core::{hashing::twox_128, storage::StorageKey};
+use sp_runtime::traits::Header;
+use std::{
+	path::Path,
+	sync::{Arc, Mutex, MutexGuard},
+};
+
+type EventRecord = frame_system::EventRecord<RuntimeEvent, Hash>;
+
+const LOG_TARGET: &str = "tictactoe-indexer";
+
+const SCHEMA: &str = "
+PRAGMA foreign_keys = ON;
+CREATE TABLE IF NOT EXISTS blocks (
+	hash BLOB PRIMARY KEY,
+	number INTEGER NOT NULL,
+	finalized INTEGER NOT NULL DEFAULT 0
+);
+CREATE TABLE IF NOT EXISTS games (
+	game_index INTEGER PRIMARY KEY,
+	host TEXT NOT NULL,
+	bet TEXT NOT NULL,
+	join_bonus TEXT NOT NULL,
+	series_length INTEGER NOT NULL,
+	board_size INTEGER NOT NULL,
+	win_length INTEGER NOT NULL,
+	created_at INTEGER NOT NULL,
+	block_hash BLOB NOT NULL REFERENCES blocks (hash) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS joins (
+	game_index INTEGER PRIMARY KEY,
+	player TEXT NOT NULL,
+	joined_at INTEGER NOT NULL,
+	block_hash BLOB NOT NULL REFERENCES blocks (hash) ON DELETE CASCADE
+);
+CREATE TABLE IF NOT EXISTS results (
+	game_index INTEGER PRIMARY KEY,
+	winner TEXT,
+	payout TEXT NOT NULL,
+	resolution TEXT NOT NULL,
+	ended_at INTEGER NOT NULL,
+	block_hash BLOB NOT NULL REFERENCES blocks (hash) ON DELETE CASCADE
+);
+CREATE INDEX IF NOT EXISTS blocks_number ON blocks (number);
+CREATE INDEX IF NOT EXISTS games_host ON games (host);
+CREATE INDEX IF NOT EXISTS games_block ON games (block_hash);
+CREATE INDEX IF NOT EXISTS joins_player ON joins (player);
+CREATE INDEX IF NOT EXISTS joins_block ON joins (block_hash);
+CREATE INDEX IF NOT EXISTS results_block ON results (block_hash);
+";
+
+const GAMES_QUERY: &str = "
+SELECT games.game_index, games.host, joins.player, games.bet, games.join_bonus,
+	games.series_length, games.board_size, games.win_length, games.created_at,
+	results.winner, results.payout, results.resolution, results.ended_at
+FROM games
+LEFT JOIN joins ON joins.game_index = games.game_index
+LEFT JOIN results ON results.game_index = games.game_index";
+
+/// Resolution recorded for games cancelled by their host or expired before anybody joined.
+const CANCELLED: &str = "cancelled";
+
+/// Status of an indexed game.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum IndexedStatus {
+	/// Waiting for a joiner or being played.
+	Open,
+	/// Decided, the winner took the jackpot.
+	Ended,
+	/// Cancelled by the host or expired before anybody joined.
+	Cancelled,
+}
+
+impl IndexedStatus {
+	fn as_str(&self) -> &'static str {
+		match self {
+			IndexedStatus::Open => "open",
+			IndexedStatus::Ended => "ended",
+			IndexedStatus::Cancelled => CANCELLED,
+		}
+	}
+}
+
+/// Indexed game. Balances are decimal strings, they may not fit in a JSON number.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameRecord {
+	pub game_index: u32,
+	pub status: IndexedStatus,
+	pub host: String,
+	pub joiner: Option<String>,
+	pub bet: String,
+	pub join_bonus: String,
+	pub series_length: u32,
+	pub board_size: u8,
+	pub win_length: u8,
+	pub created_at: BlockNumber,
+	pub winner: Option<String>,
+	/// Jackpot paid to the winner, or stake refunded to the host of a cancelled game.
+	pub payout: Option<String>,
+	/// How the game was decided, `cancelled` if the host cancelled it.
+	pub resolution: Option<String>,
+	pub ended_at: Option<BlockNumber>,
+}
+
+/// Statistics of a player over the indexed games.
+#[derive(Debug, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerStats {
+	pub player: String,
+	pub played: u32,
+	pub won: u32,
+	pub lost: u32,
+	pub open: u32,
+	pub cancelled: u32,
+	/// Bets staked on games that weren't cancelled.
+	pub staked: String,
+	/// Jackpots won.
+	pub winnings: String,
+}
+
+/// Index of tictactoe games, shared by the indexing task and the RPC methods.
+pub struct Indexer {
+	db: Mutex<Connection>,
+}
+
+impl Indexer {
+	/// Open or create the index at `path`, reverting the blocks that weren't finalized.
+	pub fn open(path: &Path) -> rusqlite::Result<Self> {
+		let db = Connection::open(path)?;
+		db.execute_batch(SCHEMA)?;
+		let reverted = db.execute("DELETE FROM blocks WHERE finalized = 0", [])?;
+		log::info!(
+			target: LOG_TARGET,
+			"Opened {}, reverted {} non-finalized blocks",
+			path.display(),
+			reverted,
+		);
+		Ok(Self { db: Mutex::new(db) })
+	}
+
+	fn db(&self) -> MutexGuard<Connection> {
+		self.db.lock().expect("indexer never panics while holding the lock; qed")
+	}
+
+	/// Returns an indexed game.
+	pub fn game(&self, game_index: u32) -> rusqlite::Result<Option<GameRecord>> {
+		self.db()
+			.query_row(
+				&format!("{} WHERE games.game_index = ?1", GAMES_QUERY),
+				params![game_index],
+				game_record,
+			)
+			.optional()
+	}
+
+	/// Returns indexed games, newest first, optionally played by `player` or with `status`.
+	pub fn games(
+		&self,
+		player: Option<&str>,
+		status: Option<IndexedStatus>,
+		offset: u32,
+		limit: u32,
+	) -> rusqlite::Result<Vec<GameRecord>> {
+		let db = self.db();
+		let mut statement = db.prepare_cached(&format!(
+			"{} WHERE (?1 IS NULL OR games.host = ?1 OR joins.player = ?1)
+				AND (?2 IS NULL OR ?2 = CASE
+					WHEN results.resolution IS NULL THEN 'open'
+					WHEN results.resolution = '{}' THEN '{}'
+					ELSE 'ended' END)
+			ORDER BY games.game_index DESC LIMIT ?3 OFFSET ?4",
+			GAMES_QUERY, CANCELLED, CANCELLED,
+		))?;
+		let games = statement
+			.query_map(
+				params![player, status.map(|status| status.as_str()), limit, offset],
+				game_record,
+			)?
+			.collect::<rusqlite::Result<_>>()?;
+		Ok(games)
+	}
+
+	/// Returns the statistics of `player` over the indexed games.
+	pub fn player_stats(&self, player: &str) -> rusqlite::Result<PlayerStats> {
+		let games = self.games(Some(player), None, 0, u32::MAX)?;
+		let (mut staked, mut winnings) = (0u128, 0u128);
+		let mut stats = PlayerStats { player: player.to_string(), ..Default::default() };
+		for game in games {
+			stats.played += 1;
+			match game.status {
+				IndexedStatus::Open => stats.open += 1,
+				IndexedStatus::Cancelled => {
+					stats.cancelled += 1;
+					continue
+				},
+				IndexedStatus::Ended if game.winner.as_deref() == Some(player) => {
+					stats.won += 1;
+					winnings += game.payout.as_deref().map_or(0, balance);
+				},
+				IndexedStatus::Ended => stats.lost += 1,
+			}
+			staked += balance(&game.bet);
+		}
+		stats.staked = staked.to_string();
+		stats.winnings = winnings.to_string();
+		Ok(stats)
+	}
+
+	/// Returns the last indexed block.
+	fn best_block(&self) -> rusqlite::Result<Option<Hash>> {
+		let hash: Option<Vec<u8>> = self
+			.db()
+			.query_row("SELECT hash FROM blocks ORDER BY number DESC LIMIT 1", [], |row| row.get(0))
+			.optional()?;
+		Ok(hash.map(|hash| Hash::from_slice(&hash)))
+	}
+
+	/// Record a block of the best chain and the games, joins and results of its events.
+	fn apply_block(
+		&self,
+		hash: Hash,
+		number: BlockNumber,
+		events: Vec<EventRecord>,
+	) -> rusqlite::Result<()> {
+		let mut db = self.db();
+		let tx = db.transaction()?;
+		tx.execute(
+			"INSERT OR REPLACE INTO blocks (hash, number) VALUES (?1, ?2)",
+			params![hash.as_bytes(), number],
+		)?;
+		for record in events {
+			let event = match record.event {
+				RuntimeEvent::Tictactoe(event) => event,
+				_ => continue,
+			};
+			match event {
+				Event::GameCreated {
+					game_index,
+					host,
+					bet,
+					series_length,
+					board_size,
+					win_length,
+					join_bonus,
+				} => tx.execute(
+					"INSERT OR REPLACE INTO games (game_index, host, bet, join_bonus, series_length,
+						board_size, win_length, created_at, block_hash)
+					VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+					params![
+						game_index,
+						host.to_string(),
+						bet.to_string(),
+						join_bonus.to_string(),
+						series_length,
+						board_size,
+						win_length,
+						number,
+						hash.as_bytes(),
+					],
+				)?,
+				Event::PlayerJoined { game_index, player, .. } => tx.execute(
+					"INSERT OR REPLACE INTO joins (game_index, player, joined_at, block_hash)
+					VALUES (?1, ?2, ?3, ?4)",
+					params![game_index, player.to_string(), number, hash.as_bytes()],
+				)?,
+				Event::GameEnded { game_index, winner, jackpot, resolution } => tx.execute(
+					"INSERT OR REPLACE INTO results (game_index, winner, payout, resolution,
+						ended_at, block_hash)
+					VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+					params![
+						game_index,
+						winner.to_string(),
+						jackpot.to_string(),
+						format!("{:?}", resolution).to_lowercase(),
+						number,
+						hash.as_bytes(),
+					],
+				)?,
+				Event::GameCancelled { game_index, refund } |
+				Event::GameExpired { game_index, refund } => tx.execute(
+					"INSERT OR REPLACE INTO results (game_index, winner, payout, resolution,
+						ended_at, block_hash)
+					VALUES (?1, NULL, ?2, ?3, ?4, ?5)",
+					params![game_index, refund.to_string(), CANCELLED, number, hash.as_bytes()],
+				)?,
+				_ => 0,
+			};
+		}
+		tx.commit()
+	}
+
+	/// Remove a block retracted from the best chain and everything indexed from it.
+	fn revert_block(&self, hash: Hash) -> rusqlite::Result<()> {
+		self.db()
+			.execute("DELETE FROM blocks WHERE hash = ?1", params![hash.as_bytes()])?;
+		Ok(())
+	}
+
+	/// Mark the indexed blocks up to `number` as finalized, they won't be reverted anymore.
+	fn finalize(&self, number: BlockNumber) -> rusqlite::Result<()> {
+		self.db().execute(
+			"UPDATE blocks SET finalized = 1 WHERE finalized = 0 AND number <= ?1",
+			params![number],
+		)?;
+		Ok(())
+	}
+
+	/// Remove every indexed block, the index was built on a chain the client doesn't know.
+	fn reset(&self) -> rusqlite::Result<()> {
+		self.db().execute("DELETE FROM blocks", [])?;
+		Ok(())
+	}
+
+	/// Index the best chain up to `best`, reverting the blocks retracted since the last indexed
+	/// block. An empty index starts with the genesis block, so the walk always resumes from a
+	/// recorded block.
+	fn sync<C, BE>(&self, client: &C, best: Hash) -> Result<(), String>
+	where
+		C: HeaderBackend<Block>
+			+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+			+ StorageProvider<Block, BE>,
+		BE: Backend<Block>,
+	{
+		let genesis = client.info().genesis_hash;
+		let from = match self.best_block().map_err(|e| e.to_string())? {
+			Some(hash) if client.header(hash).map_err(|e| e.to_string())?.is_some() => hash,
+			Some(_) => {
+				log::warn!(target: LOG_TARGET, "Indexed blocks are unknown, indexing from genesis");
+				self.reset().map_err(|e| e.to_string())?;
+				self.apply_block(genesis, 0, Vec::new()).map_err(|e| e.to_string())?;
+				genesis
+			},
+			None => {
+				self.apply_block(genesis, 0, Vec::new()).map_err(|e| e.to_string())?;
+				genesis
+			},
+		};
+
+		let route = sp_blockchain::tree_route(client, from, best).map_err(|e| e.to_string())?;
+		for block in route.retracted() {
+			self.revert_block(block.hash).map_err(|e| e.to_string())?;
+		}
+		for block in route.enacted() {
+			let events = events_at(client, block.hash)
+				.map_err(|e| format!("{}, indexed up to #{}", e, block.number.saturating_sub(1)))?;
+			self.apply_block(block.hash, block.number, events).map_err(|e| e.to_string())?;
+		}
+		Ok(())
+	}
+}
+
+/// Keep the index in sync with the best chain until the node shuts down.
+pub async fn run<C, BE>(indexer: Arc<Indexer>, client: Arc<C>)
+where
+	C: BlockchainEvents<Block>
+		+ HeaderBackend<Block>
+		+ HeaderMetadata<Block, Error = sp_blockchain::Error>
+		+ StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	// Subscribe before catching up so no block is missed.
+	let mut imports = client.import_notification_stream().fuse();
+	let mut finality = client.finality_notification_stream().fuse();
+
+	let sync = |best: Hash| {
+		if let Err(e) = indexer.sync(&*client, best) {
+			log::error!(target: LOG_TARGET, "Failed to index up to block {}: {}", best, e);
+		}
+	};
+	let finalize = |number: BlockNumber| {
+		if let Err(e) = indexer.finalize(number) {
+			log::error!(target: LOG_TARGET, "Failed to finalize block #{}: {}", number, e);
+		}
+	};
+
+	let info = client.info();
+	sync(info.best_hash);
+	finalize(info.finalized_number);
+	loop {
+		futures::select! {
+			notification = imports.next() => match notification {
+				Some(notification) if notification.is_new_best => sync(notification.hash),
+				Some(_) => {},
+				None => return,
+			},
+			notification = finality.next() => match notification {
+				Some(notification) => finalize(*notification.header.number()),
+				None => return,
+			},
+		}
+	}
+}
+
+/// Returns the events deposited in a block.
+fn events_at<C, BE>(client: &C, hash: Hash) -> Result<Vec<EventRecord>, String>
+where
+	C: StorageProvider<Block, BE>,
+	BE: Backend<Block>,
+{
+	let key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+	match client.storage(hash, &key) {
+		Ok(Some(data)) => Vec::<EventRecord>::decode(&mut &data.0[..])
+			.map_err(|e| format!("Failed to decode the events of block {}: {}", hash, e)),
+		Ok(None) => Ok(Vec::new()),
+		Err(e) => Err(format!("State of block {} is not available: {}", hash, e)),
+	}
+}
+
+fn game_record(row: &Row) -> rusqlite::Result<GameRecord> {
+	let resolution: Option<String> = row.get(11)?;
+	let status = match resolution.as_deref() {
+		None => IndexedStatus::Open,
+		Some(CANCELLED) => IndexedStatus::Cancelled,
+		Some(_) => IndexedStatus::Ended,
+	};
+	Ok(GameRecord {
+		game_index: row.get(0)?,
+		status,
+		host: row.get(1)?,
+		joiner: row.get(2)?,
+		bet: row.get(3)?,
+		join_bonus: row.get(4)?,
+		series_length: row.get(5)?,
+		board_size: row.get(6)?,
+		win_length: row.get(7)?,
+		created_at: row.get(8)?,
+		winner: row.get(9)?,
+		payout: row.get(10)?,
+		resolution,
+		ended_at: row.get(12)?,
+	})
+}
+
+/// Parse a balance stored as a decimal string.
+fn balance(value: &str) -> u128 {
+	value.parse().unwrap_or_default()
+}
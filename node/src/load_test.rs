@@ -0,0 +1,355 @@
+//! `load-test` subcommand, plays games between dev accounts against a local node over RPC to see
+//! how the chain behaves with heavy game traffic.
+//!
+//! Players are derived from `//Player<index>` and funded by Alice. Each pair of players plays its
+//! games one after the other, the host creates the game, the joiner joins it and both propose the
+//! host as winner in turn, while every pair plays at the same time. Each call waits for the block
+//! it is included in to be finalized.
+
+use crate::{benchmarking::sign_extrinsic, tictactoe};
+use codec::{Decode, Encode};
+use futures::{future::join_all, StreamExt};
+use jsonrpsee::{
+	core::{
+		client::{ClientT, Subscription, SubscriptionClientT},
+		params::ArrayParams,
+		DeserializeOwned,
+	},
+	rpc_params,
+	ws_client::{WsClient, WsClientBuilder},
+};
+use node_template_runtime::{
+	self as runtime, pallet_tictactoe, AccountId, Balance, BalancesCall, RuntimeCall, RuntimeEvent,
+	TictactoeCall,
+};
+use sc_cli::RuntimeVersion;
+use serde_json::Value;
+use sp_core::{
+	bytes::{from_hex, to_hex},
+	sr25519, Pair,
+};
+use sp_keyring::Sr25519Keyring;
+use sp_runtime::generic::Era;
+use std::{collections::BTreeMap, sync::Mutex};
+
+/// Funds sent to each player for every game on top of the bets, covering the safeguard and storage
+/// deposits, the game fee and transaction fees.
+const GAME_BUDGET: Balance = 1_000_000_000_000;
+
+/// Play games against a local development node.
+#[derive(Debug, clap::Parser)]
+pub struct LoadTestCmd {
+	/// RPC endpoint of the node.
+	#[arg(long, default_value = "ws://127.0.0.1:9944")]
+	pub url: String,
+
+	/// Number of players, paired to play. Must be even.
+	#[arg(long, default_value_t = 100)]
+	pub players: u32,
+
+	/// Number of games played by each pair of players.
+	#[arg(long, default_value_t = 1)]
+	pub games: u32,
+
+	/// Bet of the games, the minimum bet allowed if not set.
+	#[arg(long)]
+	pub bet: Option<Balance>,
+}
+
+impl LoadTestCmd {
+	/// Fund the players, play the games and print the report.
+	pub async fn run(&self) -> sc_cli::Result<()> {
+		if self.players == 0 || self.players % 2 == 1 {
+			return Err("The number of players must be even".into())
+		}
+		let node = Node::connect(&self.url).await?;
+		let bet = match self.bet {
+			Some(bet) => bet,
+			None => node.min_bet().await?,
+		};
+
+		let players: Vec<sr25519::Pair> = (0..self.players)
+			.map(|index| {
+				sr25519::Pair::from_string(&format!("//Player{}", index), None)
+					.expect("static values are valid; qed")
+			})
+			.collect();
+		let funds = (GAME_BUDGET + 2 * bet) * self.games as Balance;
+		println!("Funding {} players with {} each", players.len(), funds);
+		node.fund(&players, funds).await?;
+
+		println!("Playing {} games", self.players / 2 * self.games);
+		join_all(players.chunks(2).map(|pair| node.play(&pair[0], &pair[1], bet, self.games)))
+			.await;
+
+		node.report();
+		Ok(())
+	}
+}
+
+/// Stats of the calls of a kind.
+#[derive(Default)]
+struct CallStats {
+	/// Blocks from submission to finality of the included calls.
+	blocks_to_finality: Vec<u32>,
+	/// Number of calls that failed, by reason.
+	failures: BTreeMap<String, u32>,
+}
+
+/// Local node the games are played against.
+struct Node {
+	client: WsClient,
+	genesis_hash: runtime::Hash,
+	version: RuntimeVersion,
+	stats: Mutex<BTreeMap<&'static str, CallStats>>,
+}
+
+impl Node {
+	/// Connect to the node, only development and local chains are supported.
+	async fn connect(url: &str) -> sc_cli::Result<Self> {
+		let client = WsClientBuilder::default()
+			.build(url)
+			.await
+			.map_err(|e| format!("Failed to connect to {}: {}", url, e))?;
+		let chain_type: Value = request(&client, "system_chainType", rpc_params![]).await?;
+		if chain_type != "Development" && chain_type != "Local" {
+			return Err(format!("Load tests only run against local nodes, not {}", chain_type).into())
+		}
+		let genesis_hash = request(&client, "chain_getBlockHash", rpc_params![0]).await?;
+		let version = request(&client, "state_getRuntimeVersion", rpc_params![]).await?;
+		Ok(Self { client, genesis_hash, version, stats: Default::default() })
+	}
+
+	/// Minimum bet allowed by the pallet, or the existential deposit if there is none.
+	async fn min_bet(&self) -> sc_cli::Result<Balance> {
+		let key = to_hex(&tictactoe::storage_prefix(tictactoe::PALLET, b"BetLimits"), false);
+		let limits: Option<String> =
+			request(&self.client, "state_getStorage", rpc_params![key]).await?;
+		let min_bet = limits
+			.and_then(|limits| from_hex(&limits).ok())
+			.and_then(|limits| <(Balance, Option<Balance>)>::decode(&mut &limits[..]).ok())
+			.map_or(0, |(min_bet, _)| min_bet);
+		Ok(min_bet.max(runtime::EXISTENTIAL_DEPOSIT))
+	}
+
+	/// Transfer `funds` from Alice to every player.
+	async fn fund(&self, players: &[sr25519::Pair], funds: Balance) -> sc_cli::Result<()> {
+		let alice = Sr25519Keyring::Alice.pair();
+		let nonce = self.nonce(&alice).await?;
+		let transfers = players.iter().zip(nonce..).map(|(player, nonce)| {
+			let call = BalancesCall::transfer_keep_alive {
+				dest: AccountId::from(player.public()).into(),
+				value: funds,
+			};
+			self.submit("transfer_keep_alive", &alice, nonce, call.into())
+		});
+		let failed = join_all(transfers).await.into_iter().filter(Option::is_none).count();
+		if failed > 0 {
+			return Err(format!("Failed to fund {} players", failed).into())
+		}
+		Ok(())
+	}
+
+	/// Play `games` games between two players, stopping at the first failed call.
+	async fn play(
+		&self,
+		host: &sr25519::Pair,
+		joiner: &sr25519::Pair,
+		bet: Balance,
+		games: u32,
+	) -> Option<()> {
+		let mut host_nonce = self.nonce(host).await.ok()?;
+		let mut joiner_nonce = self.nonce(joiner).await.ok()?;
+		let winner = AccountId::from(host.public());
+
+		for _ in 0..games {
+			let create_game = TictactoeCall::create_game {
+				bet,
+				series_length: 1,
+				board_size: 3,
+				win_length: 3,
+				join_bonus: None,
+			};
+			let events = self.submit("create_game", host, host_nonce, create_game.into()).await?;
+			host_nonce += 1;
+			let game_index = events.into_iter().find_map(|event| match event {
+				RuntimeEvent::Tictactoe(pallet_tictactoe::Event::GameCreated {
+					game_index,
+					..
+				}) => Some(game_index),
+				_ => None,
+			})?;
+
+			let join_game = TictactoeCall::join_game { game_index };
+			self.submit("join_game", joiner, joiner_nonce, join_game.into()).await?;
+			joiner_nonce += 1;
+
+			// Both players propose the host as winner, one after the other so each proposal is
+			// the only fee-free call of the game in its block.
+			let end_game: RuntimeCall =
+				TictactoeCall::end_game { game_index, winner: winner.clone() }.into();
+			self.submit("end_game", host, host_nonce, end_game.clone()).await?;
+			host_nonce += 1;
+			self.submit("end_game", joiner, joiner_nonce, end_game).await?;
+			joiner_nonce += 1;
+		}
+		Some(())
+	}
+
+	/// Submit a call and wait for its block to be finalized, recording the outcome in the stats.
+	/// Returns the events of the call if it succeeded.
+	async fn submit(
+		&self,
+		name: &'static str,
+		signer: &sr25519::Pair,
+		nonce: u32,
+		call: RuntimeCall,
+	) -> Option<Vec<RuntimeEvent>> {
+		let outcome = self.submit_and_watch(signer, nonce, call).await;
+		let mut stats = self.stats.lock().expect("Stats lock is not poisoned; qed");
+		let stats = stats.entry(name).or_default();
+		match outcome {
+			Ok((blocks_to_finality, events)) => {
+				stats.blocks_to_finality.push(blocks_to_finality);
+				Some(events)
+			},
+			Err(reason) => {
+				*stats.failures.entry(reason).or_default() += 1;
+				None
+			},
+		}
+	}
+
+	/// Submit a call, returning the blocks it took to be finalized and its events, or the reason
+	/// it failed.
+	async fn submit_and_watch(
+		&self,
+		signer: &sr25519::Pair,
+		nonce: u32,
+		call: RuntimeCall,
+	) -> Result<(u32, Vec<RuntimeEvent>), String> {
+		let submitted_at = self.block_number(None).await?;
+		let extrinsic = sign_extrinsic(
+			signer,
+			call,
+			nonce,
+			Era::Immortal,
+			(self.genesis_hash, self.genesis_hash),
+			&self.version,
+		);
+		let extrinsic = to_hex(&extrinsic.encode(), false);
+
+		let mut statuses: Subscription<Value> = self
+			.client
+			.subscribe(
+				"author_submitAndWatchExtrinsic",
+				rpc_params![extrinsic.clone()],
+				"author_unwatchExtrinsic",
+			)
+			.await
+			.map_err(|e| format!("Rejected: {}", e))?;
+		let block_hash = loop {
+			let status = match statuses.next().await {
+				Some(Ok(status)) => status,
+				Some(Err(e)) => return Err(format!("Watch failed: {}", e)),
+				None => return Err("Watch closed".into()),
+			};
+			match &status {
+				Value::String(status) if status == "dropped" || status == "invalid" =>
+					return Err(format!("Transaction {}", status)),
+				Value::Object(status) =>
+					if let Some(hash) = status.get("finalized") {
+						break serde_json::from_value::<runtime::Hash>(hash.clone())
+							.map_err(|e| e.to_string())?
+					} else if status.contains_key("usurped") ||
+						status.contains_key("finalityTimeout")
+					{
+						return Err("Transaction not finalized".into())
+					},
+				_ => {},
+			}
+		};
+		let blocks_to_finality =
+			self.block_number(Some(block_hash)).await?.saturating_sub(submitted_at);
+
+		// Find the extrinsic in the block to get its events.
+		let block: Value = request(&self.client, "chain_getBlock", rpc_params![block_hash]).await?;
+		let index = block["block"]["extrinsics"]
+			.as_array()
+			.and_then(|extrinsics| extrinsics.iter().position(|e| *e == extrinsic))
+			.ok_or("Transaction not found in its block")?;
+		let key = to_hex(&tictactoe::storage_prefix(b"System", b"Events"), false);
+		let events: Option<String> =
+			request(&self.client, "state_getStorage", rpc_params![key, block_hash]).await?;
+		let events = from_hex(&events.unwrap_or_default()).map_err(|e| e.to_string())?;
+		let events =
+			Vec::<frame_system::EventRecord<RuntimeEvent, runtime::Hash>>::decode(&mut &events[..])
+				.map_err(|e| e.to_string())?
+				.into_iter()
+				.filter(|record| record.phase == frame_system::Phase::ApplyExtrinsic(index as u32))
+				.map(|record| record.event)
+				.collect::<Vec<_>>();
+
+		for event in &events {
+			if let RuntimeEvent::System(frame_system::Event::ExtrinsicFailed {
+				dispatch_error,
+				..
+			}) = event
+			{
+				return Err(format!("{:?}", dispatch_error))
+			}
+		}
+		Ok((blocks_to_finality, events))
+	}
+
+	/// Next nonce of an account, including the transactions in the pool.
+	async fn nonce(&self, account: &sr25519::Pair) -> Result<u32, String> {
+		let account = AccountId::from(account.public()).to_string();
+		request(&self.client, "system_accountNextIndex", rpc_params![account]).await
+	}
+
+	/// Number of a block, the best block if `hash` is not set.
+	async fn block_number(&self, hash: Option<runtime::Hash>) -> Result<u32, String> {
+		let header: runtime::Header =
+			request(&self.client, "chain_getHeader", rpc_params![hash]).await?;
+		Ok(header.number)
+	}
+
+	/// Print the blocks to finality and failures of each kind of call.
+	fn report(&self) {
+		let stats = self.stats.lock().expect("Stats lock is not poisoned; qed");
+		println!(
+			"{:<20}{:>10}{:>10}{:>10}{:>10}{:>10}",
+			"call", "finalized", "failed", "min", "avg", "max"
+		);
+		for (name, stats) in stats.iter() {
+			let blocks = &stats.blocks_to_finality;
+			let (min, max) = (blocks.iter().min(), blocks.iter().max());
+			let avg = blocks.iter().map(|blocks| *blocks as f64).sum::<f64>() / blocks.len() as f64;
+			println!(
+				"{:<20}{:>10}{:>10}{:>10}{:>10.1}{:>10}",
+				name,
+				blocks.len(),
+				stats.failures.values().sum::<u32>(),
+				min.map_or("-".into(), ToString::to_string),
+				avg,
+				max.map_or("-".into(), ToString::to_string),
+			);
+			for (reason, count) in &stats.failures {
+				println!("    {:>6}  {}", count, reason);
+			}
+		}
+	}
+}
+
+/// Make an RPC request, mapping errors to strings.
+async fn request<R: DeserializeOwned>(
+	client: &WsClient,
+	method: &str,
+	params: ArrayParams,
+) -> Result<R, String> {
+	client
+		.request(method, params)
+		.await
+		.map_err(|e| format!("{} failed: {}", method, e))
+}
@@ -7,7 +7,10 @@ mod service;
 mod benchmarking;
 mod cli;
 mod command;
+mod indexer;
+mod load_test;
 mod rpc;
+mod tictactoe;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
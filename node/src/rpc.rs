@@ -5,17 +5,54 @@
 
 #![warn(missing_docs)]
 
-use std::sync::Arc;
+use std::{marker::PhantomData, sync::Arc};
 
-use jsonrpsee::RpcModule;
-use node_template_runtime::{opaque::Block, AccountId, Balance, Index};
+use codec::Decode;
+use frame_support::traits::PalletInfoAccess;
+use futures::{future, stream, FutureExt, StreamExt};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::{
+		error::{CallError, ErrorObject},
+		SubscriptionResult,
+	},
+	RpcModule, SubscriptionSink,
+};
+use node_template_runtime::{opaque::Block, AccountId, Balance, Hash, Index, Runtime, Tictactoe};
+use pallet_tictactoe::GameStatus;
+use pallet_tictactoe_runtime_api::{EndGameOutcome, TictactoeApi as TictactoeRuntimeApi};
+use sc_client_api::{Backend, BlockchainEvents, StorageProvider};
+use sc_rpc::SubscriptionTaskExecutor;
 use sc_transaction_pool_api::TransactionPool;
+use serde::Serialize;
 use sp_api::ProvideRuntimeApi;
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
+use sp_core::{
+	hashing::{twox_128, twox_64},
+	storage::{StorageData, StorageKey},
+};
+use sp_runtime::{DispatchError, ModuleError};
+
+use crate::indexer::{GameRecord, IndexedStatus, Indexer, PlayerStats};
 
 pub use sc_rpc_api::DenyUnsafe;
 
+/// Games returned by `tictactoeIndex_games` when no limit is passed, and the largest limit.
+const MAX_GAMES: u32 = 100;
+
+/// Error code of failed index queries.
+const INDEX_ERROR: i32 = 1;
+
+/// Error code of failed game subscriptions.
+const SUBSCRIPTION_ERROR: i32 = 2;
+
+/// Error code of failed runtime API calls.
+const RUNTIME_ERROR: i32 = 3;
+
+type Game = pallet_tictactoe::Game<Balance, AccountId>;
+
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
 	/// The client instance to use.
@@ -24,29 +61,396 @@ pub struct FullDeps<C, P> {
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
+	/// Executor of the subscription tasks.
+	pub subscription_executor: SubscriptionTaskExecutor,
+	/// Tictactoe game index, if the indexer is enabled.
+	pub indexer: Option<Arc<Indexer>>,
+}
+
+/// Game history served by the tictactoe indexer.
+#[rpc(server)]
+pub trait TictactoeIndexApi {
+	/// Returns an indexed game.
+	#[method(name = "tictactoeIndex_game")]
+	fn game(&self, game_index: u32) -> RpcResult<Option<GameRecord>>;
+
+	/// Returns indexed games, newest first, optionally played by `player` or with `status`.
+	/// At most `limit` games are returned, up to 100, after skipping `offset` games.
+	#[method(name = "tictactoeIndex_games")]
+	fn games(
+		&self,
+		player: Option<AccountId>,
+		status: Option<IndexedStatus>,
+		offset: Option<u32>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<GameRecord>>;
+
+	/// Returns the statistics of a player over the indexed games.
+	#[method(name = "tictactoeIndex_playerStats")]
+	fn player_stats(&self, player: AccountId) -> RpcResult<PlayerStats>;
+}
+
+/// Serves the games indexed by the tictactoe indexer.
+pub struct TictactoeIndex {
+	indexer: Arc<Indexer>,
+}
+
+impl TictactoeIndex {
+	/// Create a new instance serving `indexer`.
+	pub fn new(indexer: Arc<Indexer>) -> Self {
+		Self { indexer }
+	}
+}
+
+impl TictactoeIndexApiServer for TictactoeIndex {
+	fn game(&self, game_index: u32) -> RpcResult<Option<GameRecord>> {
+		self.indexer.game(game_index).map_err(index_error)
+	}
+
+	fn games(
+		&self,
+		player: Option<AccountId>,
+		status: Option<IndexedStatus>,
+		offset: Option<u32>,
+		limit: Option<u32>,
+	) -> RpcResult<Vec<GameRecord>> {
+		let player = player.map(|player| player.to_string());
+		let limit = limit.unwrap_or(MAX_GAMES).min(MAX_GAMES);
+		self.indexer
+			.games(player.as_deref(), status, offset.unwrap_or_default(), limit)
+			.map_err(index_error)
+	}
+
+	fn player_stats(&self, player: AccountId) -> RpcResult<PlayerStats> {
+		self.indexer.player_stats(&player.to_string()).map_err(index_error)
+	}
+}
+
+fn index_error(e: rusqlite::Error) -> JsonRpseeError {
+	call_error(INDEX_ERROR, "Failed to query the tictactoe index", e)
+}
+
+fn call_error(code: i32, message: &str, e: impl ToString) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(code, message, Some(e.to_string()))).into()
+}
+
+/// Game pushed to `tictactoe_subscribeGame` subscribers.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameView {
+	/// Index of the game.
+	pub game_index: u32,
+	/// Block the game was read at.
+	pub block: Hash,
+	/// Whether the block is finalized.
+	pub finalized: bool,
+	/// The game, `None` if it doesn't exist or has been pruned.
+	pub game: Option<GameState>,
+}
+
+/// Decoded `Games` entry. Balances are decimal strings, they may not fit in a JSON number.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameState {
+	/// `open`, `inMediation` or `ended`.
+	pub status: &'static str,
+	/// Host of the game.
+	pub host: Option<AccountId>,
+	/// Joiner of the game, `None` until somebody joins.
+	pub joiner: Option<AccountId>,
+	/// Winner proposed by the host.
+	pub host_winner: Option<AccountId>,
+	/// Winner proposed by the joiner.
+	pub joiner_winner: Option<AccountId>,
+	/// Bet each player stakes.
+	pub bet: String,
+	/// Bonus paid to the joiner.
+	pub join_bonus: String,
+	/// Number of rounds of the series.
+	pub series_length: u32,
+	/// Rounds won by the host and joiner.
+	pub round_wins: (u32, u32),
+	/// Width and height of the board.
+	pub board_size: u8,
+	/// Marks in a row needed to win.
+	pub win_length: u8,
+	/// Tournament of the game, if it is a bracket game.
+	pub tournament: Option<u32>,
+}
+
+impl From<Game> for GameState {
+	fn from(game: Game) -> Self {
+		let (host, joiner) = game.payout_addresses().clone();
+		let (host_winner, joiner_winner) = game.handshake().clone();
+		let (board_size, win_length) = game.board();
+		Self {
+			status: match game.status() {
+				GameStatus::Open => "open",
+				GameStatus::InMediation => "inMediation",
+				GameStatus::Ended => "ended",
+			},
+			host,
+			joiner,
+			host_winner,
+			joiner_winner,
+			bet: game.bet().to_string(),
+			join_bonus: game.join_bonus().to_string(),
+			series_length: game.series_length(),
+			round_wins: game.round_wins(),
+			board_size,
+			win_length,
+			tournament: game.tournament(),
+		}
+	}
+}
+
+/// Predicted outcome of an `end_game` call, tagged by `outcome`.
+#[derive(Debug, Serialize)]
+#[serde(tag = "outcome", rename_all = "camelCase")]
+pub enum EndGameSimulation {
+	/// The proposal is recorded, waiting for the other player.
+	ProposalRecorded,
+	/// The players proposed different winners, the game is sent to mediation.
+	MediationNeeded,
+	/// The winner takes the round and the series goes on.
+	#[serde(rename_all = "camelCase")]
+	RoundWon {
+		/// Winner of the round.
+		winner: AccountId,
+		/// Rounds won by the host and joiner after the call.
+		round_wins: (u32, u32),
+	},
+	/// The game is decided.
+	Settled {
+		/// Winner of the game.
+		winner: AccountId,
+		/// Amount received by each player and side bettor.
+		payouts: Vec<Payout>,
+	},
+	/// The call fails.
+	Failed {
+		/// Name of the pallet error, or the dispatch error if it doesn't come from the pallet.
+		error: String,
+	},
+}
+
+/// Amount received by an account when a game is settled.
+#[derive(Debug, Serialize)]
+pub struct Payout {
+	/// Receiving account.
+	pub account: AccountId,
+	/// Received amount, as a decimal string.
+	pub amount: String,
+}
+
+impl From<EndGameOutcome<AccountId, Balance>> for EndGameSimulation {
+	fn from(outcome: EndGameOutcome<AccountId, Balance>) -> Self {
+		match outcome {
+			EndGameOutcome::ProposalRecorded => Self::ProposalRecorded,
+			EndGameOutcome::MediationNeeded => Self::MediationNeeded,
+			EndGameOutcome::RoundWon { winner, round_wins } =>
+				Self::RoundWon { winner, round_wins },
+			EndGameOutcome::Settled { winner, payouts } => Self::Settled {
+				winner,
+				payouts: payouts
+					.into_iter()
+					.map(|(account, amount)| Payout { account, amount: amount.to_string() })
+					.collect(),
+			},
+			EndGameOutcome::Failed(error) => Self::Failed { error: error_name(error) },
+		}
+	}
+}
+
+/// Returns the variant name of tictactoe pallet errors, the debug output of other errors.
+fn error_name(error: DispatchError) -> String {
+	if let DispatchError::Module(ModuleError { index, error: bytes, .. }) = error {
+		if index as usize == Tictactoe::index() {
+			if let Ok(error) = pallet_tictactoe::Error::<Runtime>::decode(&mut &bytes[..]) {
+				return <&'static str>::from(error).into()
+			}
+		}
+	}
+	format!("{:?}", error)
+}
+
+/// Live updates and dry runs of tictactoe games.
+#[rpc(server)]
+pub trait TictactoeApi {
+	/// Subscribe to a game. The game is pushed as of the finalized block and, if it differs, as of
+	/// the best block. Then it is pushed whenever an imported best block changes its `Games` entry
+	/// and whenever a finalized block settles a different entry than the last finalized one.
+	#[subscription(
+		name = "tictactoe_subscribeGame",
+		unsubscribe = "tictactoe_unsubscribeGame",
+		item = GameView
+	)]
+	fn subscribe_game(&self, game_index: u32);
+
+	/// Predict the outcome of `end_game` called by `caller` proposing `winner`, as of block `at`
+	/// or the best block. Nothing is submitted.
+	#[method(name = "tictactoe_simulateEndGame")]
+	fn simulate_end_game(
+		&self,
+		game_index: u32,
+		caller: AccountId,
+		winner: AccountId,
+		at: Option<Hash>,
+	) -> RpcResult<EndGameSimulation>;
+}
+
+/// Pushes game updates from the storage change notifications of the client and simulates calls
+/// with its runtime API.
+pub struct TictactoeGames<C, BE> {
+	client: Arc<C>,
+	executor: SubscriptionTaskExecutor,
+	_phantom: PhantomData<BE>,
+}
+
+impl<C, BE> TictactoeGames<C, BE> {
+	/// Create a new instance reading games from `client`.
+	pub fn new(client: Arc<C>, executor: SubscriptionTaskExecutor) -> Self {
+		Self { client, executor, _phantom: PhantomData }
+	}
+}
+
+/// Update of the `Games` entry of a subscribed game.
+enum GameUpdate {
+	/// The entry as of the best block when subscribing.
+	Best(Hash),
+	/// An imported best block changed the entry.
+	Changed(Hash, Option<StorageData>),
+	/// A block has been finalized.
+	Finalized(Hash),
+}
+
+impl<C, BE> TictactoeApiServer for TictactoeGames<C, BE>
+where
+	C: BlockchainEvents<Block> + HeaderBackend<Block> + StorageProvider<Block, BE>,
+	C: ProvideRuntimeApi<Block> + Send + Sync + 'static,
+	C::Api: TictactoeRuntimeApi<Block, AccountId, Balance>,
+	BE: Backend<Block> + 'static,
+{
+	fn subscribe_game(&self, mut sink: SubscriptionSink, game_index: u32) -> SubscriptionResult {
+		let key = game_key(game_index);
+		let changes =
+			match self.client.storage_changes_notification_stream(Some(&[key.clone()]), None) {
+				Ok(changes) => changes,
+				Err(e) => {
+					let _ = sink.reject(call_error(
+						SUBSCRIPTION_ERROR,
+						"Failed to subscribe to storage changes",
+						e,
+					));
+					return Ok(())
+				},
+			};
+		let changed_key = key.clone();
+		let changes = changes.map(move |notification| {
+			let data = notification
+				.changes
+				.iter()
+				.find(|(child_key, key, _)| child_key.is_none() && *key == &changed_key)
+				.and_then(|(_, _, data)| data.cloned());
+			GameUpdate::Changed(notification.block, data)
+		});
+		let finality = self
+			.client
+			.finality_notification_stream()
+			.map(|notification| GameUpdate::Finalized(notification.hash));
+
+		let info = self.client.info();
+		let client = self.client.clone();
+		let mut last_finalized = None;
+		let views = stream::iter([
+			GameUpdate::Finalized(info.finalized_hash),
+			GameUpdate::Best(info.best_hash),
+		])
+		.chain(stream::select(changes, finality))
+		.filter_map(move |update| {
+			let view = match update {
+				GameUpdate::Changed(block, data) => Some(game_view(game_index, block, false, data)),
+				GameUpdate::Best(block) => client
+					.storage(block, &key)
+					.ok()
+					.filter(|data| Some(data) != last_finalized.as_ref())
+					.map(|data| game_view(game_index, block, false, data)),
+				GameUpdate::Finalized(block) => client
+					.storage(block, &key)
+					.ok()
+					.filter(|data| Some(data) != last_finalized.as_ref())
+					.map(|data| {
+						last_finalized = Some(data.clone());
+						game_view(game_index, block, true, data)
+					}),
+			};
+			future::ready(view)
+		});
+
+		let fut = async move {
+			sink.pipe_from_stream(views).await;
+		};
+		self.executor.spawn("tictactoe-rpc-subscription", Some("rpc"), fut.boxed());
+		Ok(())
+	}
+
+	fn simulate_end_game(
+		&self,
+		game_index: u32,
+		caller: AccountId,
+		winner: AccountId,
+		at: Option<Hash>,
+	) -> RpcResult<EndGameSimulation> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.simulate_end_game(at, game_index, caller, winner)
+			.map(EndGameSimulation::from)
+			.map_err(|e| call_error(RUNTIME_ERROR, "Unable to simulate end_game", e))
+	}
+}
+
+/// Returns the storage key of the `Games` entry of a game.
+fn game_key(game_index: u32) -> StorageKey {
+	let index = game_index.to_le_bytes();
+	StorageKey(
+		[&twox_128(b"Tictactoe")[..], &twox_128(b"Games"), &twox_64(&index), &index].concat(),
+	)
+}
+
+fn game_view(game_index: u32, block: Hash, finalized: bool, data: Option<StorageData>) -> GameView {
+	let game = data.and_then(|data| Game::decode(&mut &data.0[..]).ok()).map(GameState::from);
+	GameView { game_index, block, finalized, game }
 }
 
 /// Instantiate all full RPC extensions.
-pub fn create_full<C, P>(
+pub fn create_full<C, P, BE>(
 	deps: FullDeps<C, P>,
 ) -> Result<RpcModule<()>, Box<dyn std::error::Error + Send + Sync>>
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
+	C: BlockchainEvents<Block> + StorageProvider<Block, BE>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
+	C::Api: TictactoeRuntimeApi<Block, AccountId, Balance>,
 	P: TransactionPool + 'static,
+	BE: Backend<Block> + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut module = RpcModule::new(());
-	let FullDeps { client, pool, deny_unsafe } = deps;
+	let FullDeps { client, pool, deny_unsafe, subscription_executor, indexer } = deps;
 
 	module.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
-	module.merge(TransactionPayment::new(client).into_rpc())?;
+	module.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	module.merge(TictactoeGames::<C, BE>::new(client, subscription_executor).into_rpc())?;
+	if let Some(indexer) = indexer {
+		module.merge(TictactoeIndex::new(indexer).into_rpc())?;
+	}
 
 	// Extend this RPC with a custom API by using the following syntax.
 	// `YourRpcStruct` should have a reference to a client, which is needed
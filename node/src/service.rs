@@ -5,7 +5,9 @@ use sc_client_api::BlockBackend;
 use sc_consensus_aura::{ImportQueueParams, SlotProportion, StartAuraParams};
 use sc_consensus_grandpa::SharedVoterState;
 pub use sc_executor::NativeElseWasmExecutor;
-use sc_service::{error::Error as ServiceError, Configuration, TaskManager, WarpSyncParams};
+use sc_service::{
+	error::Error as ServiceError, Configuration, PruningMode, TaskManager, WarpSyncParams,
+};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sp_consensus_aura::sr25519::AuthorityPair as AuraPair;
 use std::{sync::Arc, time::Duration};
@@ -138,7 +140,11 @@ pub fn new_partial(
 }
 
 /// Builds a new service for a full client.
-pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError> {
+/// The tictactoe indexer is started when `tictactoe_indexer` is set.
+pub fn new_full(
+	mut config: Configuration,
+	tictactoe_indexer: bool,
+) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -192,14 +198,47 @@ pub fn new_full(mut config: Configuration) -> Result<TaskManager, ServiceError>
 	let enable_grandpa = !config.disable_grandpa;
 	let prometheus_registry = config.prometheus_registry().cloned();
 
+	let indexer = if tictactoe_indexer {
+		// Events are read from the state of every block, pruned blocks could never be indexed.
+		if !config.state_pruning.as_ref().map_or(false, PruningMode::is_archive) {
+			return Err(ServiceError::Other(
+				"The tictactoe indexer requires `--state-pruning archive`".into(),
+			))
+		}
+		// The index is kept next to the chain database.
+		let path = config
+			.database
+			.path()
+			.ok_or_else(|| {
+				ServiceError::Other("The tictactoe indexer requires an on-disk database".into())
+			})?
+			.with_file_name("tictactoe-index.sqlite");
+		let indexer = Arc::new(crate::indexer::Indexer::open(&path).map_err(|e| {
+			ServiceError::Other(format!("Failed to open the tictactoe index: {}", e))
+		})?);
+		task_manager.spawn_handle().spawn_blocking(
+			"tictactoe-indexer",
+			None,
+			crate::indexer::run(indexer.clone(), client.clone()),
+		);
+		Some(indexer)
+	} else {
+		None
+	};
+
 	let rpc_extensions_builder = {
 		let client = client.clone();
 		let pool = transaction_pool.clone();
 
-		Box::new(move |deny_unsafe, _| {
-			let deps =
-				crate::rpc::FullDeps { client: client.clone(), pool: pool.clone(), deny_unsafe };
-			crate::rpc::create_full(deps).map_err(Into::into)
+		Box::new(move |deny_unsafe, subscription_executor| {
+			let deps = crate::rpc::FullDeps {
+				client: client.clone(),
+				pool: pool.clone(),
+				deny_unsafe,
+				subscription_executor,
+				indexer: indexer.clone(),
+			};
+			crate::rpc::create_full::<_, _, FullBackend>(deps).map_err(Into::into)
 		})
 	};
 
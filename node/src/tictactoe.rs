@@ -0,0 +1,354 @@
+//! `tictactoe` subcommand, inspects the tictactoe pallet state in the local database without
+//! running the node.
+
+use codec::{Decode, Encode};
+use node_template_runtime::{opaque::Block, AccountId, Balance, Index, Runtime, Tictactoe};
+use pallet_tictactoe::{GameRules, GameStatus, SideBet, Tournament};
+use sc_cli::{BlockNumberOrHash, CliConfiguration, DatabaseParams, PruningParams, SharedParams};
+use sc_client_api::StorageProvider;
+use serde_json::{json, Value};
+use sp_blockchain::HeaderBackend;
+use sp_core::{
+	hashing::{blake2_128, twox_128, twox_64},
+	storage::StorageKey,
+};
+use std::{marker::PhantomData, sync::Arc};
+
+type Game = pallet_tictactoe::Game<Balance, AccountId>;
+type Board = <<Runtime as pallet_tictactoe::Config>::Rules as GameRules>::Board;
+type AccountInfo = frame_system::AccountInfo<Index, <Runtime as frame_system::Config>::AccountData>;
+
+/// Name of the pallet in `construct_runtime!`, prefix of its storage keys.
+pub(crate) const PALLET: &[u8] = b"Tictactoe";
+
+/// Inspect the state of the tictactoe pallet.
+#[derive(Debug, clap::Parser)]
+pub struct TictactoeCmd {
+	#[command(subcommand)]
+	pub query: Query,
+
+	/// Block hash or number to inspect, the best block if not set.
+	#[arg(long, value_name = "HASH or NUMBER", global = true)]
+	pub at: Option<BlockNumberOrHash>,
+
+	/// Output format.
+	#[arg(long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+	pub output: OutputFormat,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub shared_params: SharedParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub pruning_params: PruningParams,
+
+	#[allow(missing_docs)]
+	#[clap(flatten)]
+	pub database_params: DatabaseParams,
+}
+
+/// Queries of the `tictactoe` subcommand.
+#[derive(Debug, clap::Subcommand)]
+pub enum Query {
+	/// List games, optionally filtered by status.
+	Games {
+		/// Only list games with this status.
+		#[arg(long, value_enum)]
+		status: Option<StatusFilter>,
+	},
+
+	/// Show the full record of a game.
+	Game {
+		/// Index of the game.
+		game_index: u32,
+	},
+
+	/// Compare the balance of the pallet account with the funds it holds for games, side bets
+	/// and tournaments.
+	Solvency,
+}
+
+/// Status of the listed games.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum StatusFilter {
+	/// Games being played or waiting for a joiner.
+	Open,
+	/// Games whose players proposed different winners.
+	InMediation,
+	/// Ended games that haven't been pruned.
+	Ended,
+}
+
+impl From<StatusFilter> for GameStatus {
+	fn from(filter: StatusFilter) -> Self {
+		match filter {
+			StatusFilter::Open => GameStatus::Open,
+			StatusFilter::InMediation => GameStatus::InMediation,
+			StatusFilter::Ended => GameStatus::Ended,
+		}
+	}
+}
+
+/// Output format of the queries.
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum OutputFormat {
+	/// Aligned columns, one game per row.
+	Table,
+	/// Pretty printed JSON.
+	Json,
+}
+
+impl TictactoeCmd {
+	/// Run the query against the state of the selected block.
+	pub fn run<C, BE>(&self, client: Arc<C>) -> sc_cli::Result<()>
+	where
+		C: StorageProvider<Block, BE> + HeaderBackend<Block>,
+		BE: sc_client_api::Backend<Block>,
+	{
+		let hash = match &self.at {
+			Some(at) => client.expect_block_hash_from_id(&at.parse::<Block>()?)?,
+			None => client.info().best_hash,
+		};
+		let state = State::<C, BE> { client, hash, _phantom: PhantomData };
+
+		let output = match &self.query {
+			Query::Games { status } => {
+				let games = state.games()?.into_iter().filter(|(_, game)| {
+					status.map_or(true, |status| game.status() == status.into())
+				});
+				Output::Rows(games.map(|(game_index, game)| game_json(game_index, &game)).collect())
+			},
+			Query::Game { game_index } => {
+				let game: Game = state
+					.value(map_key(b"Games", *game_index))?
+					.ok_or_else(|| format!("Game {} does not exist", game_index))?;
+				let board: Option<Board> = state.value(map_key(b"Boards", *game_index))?;
+				let move_deadline: Option<u32> =
+					state.value(map_key(b"MoveDeadlines", *game_index))?;
+				let (host_winner, joiner_winner) = game.handshake();
+
+				let mut record = game_json(*game_index, &game);
+				record["host_winner"] = json!(host_winner.as_ref().map(ToString::to_string));
+				record["joiner_winner"] = json!(joiner_winner.as_ref().map(ToString::to_string));
+				record["round_wins"] = json!(game.round_wins());
+				record["house_difficulty"] = json!(game.house_difficulty());
+				record["join_bonus"] = json!(game.join_bonus().to_string());
+				record["storage_deposit"] = json!(game.storage_deposit().to_string());
+				record["board"] = json!(board.map(|board| format!("{:?}", board)));
+				record["move_deadline"] = json!(move_deadline);
+				Output::Record(record)
+			},
+			Query::Solvency => Output::Record(state.solvency()?),
+		};
+
+		match self.output {
+			OutputFormat::Json => println!("{}", output.json()),
+			OutputFormat::Table => output.print_table(),
+		}
+		Ok(())
+	}
+}
+
+impl CliConfiguration for TictactoeCmd {
+	fn shared_params(&self) -> &SharedParams {
+		&self.shared_params
+	}
+
+	fn pruning_params(&self) -> Option<&PruningParams> {
+		Some(&self.pruning_params)
+	}
+
+	fn database_params(&self) -> Option<&DatabaseParams> {
+		Some(&self.database_params)
+	}
+}
+
+/// State of a block in the local database.
+struct State<C, BE> {
+	client: Arc<C>,
+	hash: <Block as sp_runtime::traits::Block>::Hash,
+	_phantom: PhantomData<BE>,
+}
+
+impl<C, BE> State<C, BE>
+where
+	C: StorageProvider<Block, BE>,
+	BE: sc_client_api::Backend<Block>,
+{
+	/// Read and decode a storage value.
+	fn value<T: Decode>(&self, key: Vec<u8>) -> sc_cli::Result<Option<T>> {
+		self.client
+			.storage(self.hash, &StorageKey(key))?
+			.map(|data| T::decode(&mut &data.0[..]))
+			.transpose()
+			.map_err(|e| format!("Failed to decode storage value: {}", e).into())
+	}
+
+	/// Read and decode every value of a storage map, along with the key the value is stored at.
+	fn pairs<T: Decode>(&self, item: &[u8]) -> sc_cli::Result<Vec<(Vec<u8>, T)>> {
+		let prefix = StorageKey(storage_prefix(PALLET, item));
+		self.client
+			.storage_pairs(self.hash, Some(&prefix), None)?
+			.map(|(key, data)| -> sc_cli::Result<_> {
+				let value = T::decode(&mut &data.0[..])
+					.map_err(|e| format!("Failed to decode storage value: {}", e))?;
+				Ok((key.0, value))
+			})
+			.collect()
+	}
+
+	/// Returns every game stored, in index order.
+	fn games(&self) -> sc_cli::Result<Vec<(u32, Game)>> {
+		let mut games = self
+			.pairs::<Game>(b"Games")?
+			.into_iter()
+			.map(|(key, game)| Ok((map_key_index(&key)?, game)))
+			.collect::<sc_cli::Result<Vec<_>>>()?;
+		games.sort_by_key(|(game_index, _)| *game_index);
+		Ok(games)
+	}
+
+	/// Compare the free balance of the pallet account with the funds held for games, side bets
+	/// and tournaments. Game stakes are estimated with the current safeguard deposit.
+	fn solvency(&self) -> sc_cli::Result<Value> {
+		let account = Tictactoe::account_id();
+		let encoded = account.encode();
+		let account_key =
+			[storage_prefix(b"System", b"Account"), blake2_128(&encoded).to_vec(), encoded]
+				.concat();
+		let balance = self.value::<AccountInfo>(account_key)?.map_or(0, |info| info.data.free);
+		let safeguard_deposit = self
+			.value::<Balance>(storage_prefix(PALLET, b"SafeguardDeposit"))?
+			.unwrap_or_default();
+
+		let games: Balance =
+			self.games()?.iter().map(|(_, game)| game.held_funds(safeguard_deposit)).sum();
+		let side_bets: Balance = self
+			.pairs::<Vec<SideBet<Balance, AccountId>>>(b"SideBets")?
+			.iter()
+			.flat_map(|(_, bets)| bets.iter().map(|bet| bet.amount()))
+			.sum();
+		let tournaments: Balance = self
+			.pairs::<Tournament<Runtime>>(b"Tournaments")?
+			.iter()
+			.map(|(_, tournament)| tournament.held_funds())
+			.sum();
+		let held = games + side_bets + tournaments;
+		// Balances are rendered as decimal strings, they don't fit JSON numbers.
+		let surplus = if balance >= held {
+			(balance - held).to_string()
+		} else {
+			format!("-{}", held - balance)
+		};
+
+		Ok(json!({
+			"account": account.to_string(),
+			"balance": balance.to_string(),
+			"games": games.to_string(),
+			"side_bets": side_bets.to_string(),
+			"tournaments": tournaments.to_string(),
+			"held": held.to_string(),
+			"surplus": surplus,
+			"solvent": balance >= held,
+		}))
+	}
+}
+
+/// Query output, printed as JSON or as a table.
+enum Output {
+	Rows(Vec<Value>),
+	Record(Value),
+}
+
+impl Output {
+	fn json(&self) -> String {
+		let value = match self {
+			Output::Rows(rows) => json!(rows),
+			Output::Record(record) => record.clone(),
+		};
+		serde_json::to_string_pretty(&value).expect("JSON values serialize; qed")
+	}
+
+	fn print_table(&self) {
+		match self {
+			Output::Rows(rows) => {
+				let columns = match rows.first().and_then(Value::as_object) {
+					Some(columns) => columns,
+					None => return println!("No games found"),
+				};
+				let header: Vec<&str> = columns.keys().map(String::as_str).collect();
+				let cells: Vec<Vec<String>> = rows
+					.iter()
+					.map(|row| header.iter().map(|column| cell(&row[*column])).collect())
+					.collect();
+				let widths: Vec<usize> = header
+					.iter()
+					.enumerate()
+					.map(|(i, column)| {
+						cells.iter().map(|row| row[i].len()).max().unwrap_or(0).max(column.len())
+					})
+					.collect();
+				let line = |row: Vec<String>| {
+					row.iter()
+						.zip(&widths)
+						.map(|(cell, width)| format!("{:<width$}", cell, width = width))
+						.collect::<Vec<_>>()
+						.join("  ")
+				};
+				println!("{}", line(header.iter().map(|column| column.to_string()).collect()));
+				for row in cells {
+					println!("{}", line(row));
+				}
+			},
+			Output::Record(record) =>
+				for (field, value) in record.as_object().into_iter().flatten() {
+					println!("{:<18}{}", field, cell(value));
+				},
+		}
+	}
+}
+
+/// Summary of a game, shared by the list and the full record.
+fn game_json(game_index: u32, game: &Game) -> Value {
+	let (host, joiner) = game.payout_addresses();
+	let (board_size, win_length) = game.board();
+	json!({
+		"game_index": game_index,
+		"status": format!("{:?}", game.status()),
+		"host": host.as_ref().map(ToString::to_string),
+		"joiner": joiner.as_ref().map(ToString::to_string),
+		"bet": game.bet().to_string(),
+		"series_length": game.series_length(),
+		"board": format!("{}x{}/{}", board_size, board_size, win_length),
+		"tournament": game.tournament(),
+	})
+}
+
+/// Format a JSON value for a table cell.
+fn cell(value: &Value) -> String {
+	match value {
+		Value::Null => "-".into(),
+		Value::String(value) => value.clone(),
+		value => value.to_string(),
+	}
+}
+
+/// Returns the prefix of the keys of a storage item.
+pub(crate) fn storage_prefix(pallet: &[u8], item: &[u8]) -> Vec<u8> {
+	[twox_128(pallet), twox_128(item)].concat()
+}
+
+/// Returns the key of an entry of a `Twox64Concat` hashed map keyed by `u32`.
+pub(crate) fn map_key(item: &[u8], key: u32) -> Vec<u8> {
+	let key = key.to_le_bytes();
+	[storage_prefix(PALLET, item), twox_64(&key).to_vec(), key.to_vec()].concat()
+}
+
+/// Returns the `u32` key of a `Twox64Concat` hashed map entry.
+fn map_key_index(key: &[u8]) -> sc_cli::Result<u32> {
+	key.get(40..)
+		.and_then(|index| index.try_into().ok())
+		.map(u32::from_le_bytes)
+		.ok_or_else(|| "Unexpected storage key".into())
+}
@@ -0,0 +1,24 @@
+//! Runtime API of the tictactoe pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+
+pub use pallet_tictactoe::EndGameOutcome;
+
+sp_api::decl_runtime_apis! {
+	/// Read-only queries of the tictactoe pallet.
+	pub trait TictactoeApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
+	{
+		/// Predict the outcome of `end_game` called by `caller` proposing `winner`, without
+		/// changing the state.
+		fn simulate_end_game(
+			game_index: u32,
+			caller: AccountId,
+			winner: AccountId,
+		) -> EndGameOutcome<AccountId, Balance>;
+	}
+}
@@ -10,7 +10,10 @@ use frame_benchmarking::{
 	v2::*,
 };
 use frame_support::{
-	sp_runtime::traits::{Bounded, Zero},
+	sp_runtime::{
+		traits::{Bounded, Zero},
+		Permill,
+	},
 	storage::bounded_vec::BoundedVec,
 	traits::{EnsureOrigin, OnInitialize},
 };
@@ -19,6 +22,168 @@ use frame_system::RawOrigin;
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+/// Assert that `generic_event` is the last event deposited.
+fn assert_last_event<T: Config>(generic_event: <T as Config>::RuntimeEvent) {
+	frame_system::Pallet::<T>::assert_last_event(generic_event.into());
+}
+
+/// Create a game hosted by `host` and joined by the whitelisted caller, returning both players.
+/// The game fee is set to the existential deposit so the pallet account is kept alive by the fee
+/// of the game itself, and the safeguard deposit is set so that both deposits are transferred.
+/// The block number is set so that events are deposited.
+fn create_joined_game<T: Config>(bet: BalanceOf<T>) -> (T::AccountId, T::AccountId) {
+	frame_system::Pallet::<T>::set_block_number(1u32.into());
+	let _ = Tictactoe::<T>::set_game_fee(RawOrigin::Root.into(), T::Currency::minimum_balance());
+	let _ = Tictactoe::<T>::set_safeguard_deposit(RawOrigin::Root.into(), bet);
+
+	let host: T::AccountId = account("host", 0, 0);
+	T::Currency::make_free_balance_be(&host, 10000000u32.into());
+	let _ = Tictactoe::<T>::create_game(RawOrigin::Signed(host.clone()).into(), bet, 1, 3, 3, None);
+	let joiner: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&joiner, 10000000u32.into());
+	let _ = Tictactoe::<T>::join_game(RawOrigin::Signed(joiner.clone()).into(), 0u32);
+
+	assert_eq!(
+		Tictactoe::<T>::games(0).unwrap().payout_addresses,
+		(Some(host.clone()), Some(joiner.clone()))
+	);
+	(host, joiner)
+}
+
+/// Register a session key acting for `player` on game 0, the longest way to resolve a caller.
+fn session_key_of<T: Config>(player: &T::AccountId) -> T::AccountId {
+	let session_key: T::AccountId = account("session", 0, 0);
+	let _ = Tictactoe::<T>::set_session_key(
+		RawOrigin::Signed(player.clone()).into(),
+		session_key.clone(),
+		Some(0u32),
+	);
+	session_key
+}
+
+/// Create a game, joined by the whitelisted caller, and end it in favor of its host.
+fn create_ended_game<T: Config>() -> T::AccountId {
+	T::Currency::make_free_balance_be(&Tictactoe::<T>::account_id(), 1000u32.into());
+	let host: T::AccountId = account("host", 0, 0);
+	T::Currency::make_free_balance_be(&host, 10000000u32.into());
+	let _ = Tictactoe::<T>::create_game(
+		RawOrigin::Signed(host.clone()).into(),
+		T::Currency::minimum_balance(),
+		1,
+		3,
+		3,
+		None,
+	);
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, 10000000u32.into());
+	let _ = Tictactoe::<T>::join_game(RawOrigin::Signed(caller.clone()).into(), 0u32);
+	let _ = Tictactoe::<T>::end_game(RawOrigin::Signed(host.clone()).into(), 0u32, host.clone());
+	let _ = Tictactoe::<T>::end_game(RawOrigin::Signed(caller.clone()).into(), 0u32, host);
+	caller
+}
+
+/// Largest bracket size that fits in `p` players, brackets must be a power of two.
+fn bracket_size(p: u32) -> u32 {
+	1 << (31 - p.leading_zeros())
+}
+
+/// Create a tournament with room for `max_players` and register `registered` funded players.
+/// Prizes are split evenly across every paid standing to exercise the longest payout.
+fn create_tournament_with_players<T: Config>(
+	max_players: u32,
+	registered: u32,
+) -> Vec<T::AccountId> {
+	let organizer: T::AccountId = account("organizer", 0, 0);
+	let positions = T::MaxPrizePositions::get().min(max_players);
+	let prize_distribution =
+		(0..positions).map(|_| Permill::from_rational(1u32, positions)).collect();
+	let _ = Tictactoe::<T>::create_tournament(
+		RawOrigin::Signed(organizer).into(),
+		T::Currency::minimum_balance(),
+		max_players,
+		prize_distribution,
+		Permill::from_percent(10),
+	);
+
+	let tournament_index = Tictactoe::<T>::tournament_index() - 1;
+	(0..registered)
+		.map(|i| {
+			let player: T::AccountId = account("player", i, 0);
+			T::Currency::make_free_balance_be(&player, 10000000u32.into());
+			let _ = Tictactoe::<T>::register_for_tournament(
+				RawOrigin::Signed(player.clone()).into(),
+				tournament_index,
+			);
+			player
+		})
+		.collect()
+}
+
+/// Create a game joined by two funded players and place `bets` side bets on the host, each from a
+/// different funded bettor.
+fn create_game_with_side_bets<T: Config>(bets: u32) {
+	let host: T::AccountId = account("host", 0, 0);
+	let joiner: T::AccountId = account("joiner", 0, 0);
+	T::Currency::make_free_balance_be(&host, 10000000u32.into());
+	T::Currency::make_free_balance_be(&joiner, 10000000u32.into());
+	let _ = Tictactoe::<T>::create_game(
+		RawOrigin::Signed(host).into(),
+		T::Currency::minimum_balance(),
+		1,
+		3,
+		3,
+		None,
+	);
+	let _ = Tictactoe::<T>::join_game(RawOrigin::Signed(joiner).into(), 0u32);
+	place_side_bets::<T>(0u32, bets);
+}
+
+/// Place `bets` side bets on the host of `game_index`, each from a different funded bettor.
+fn place_side_bets<T: Config>(game_index: u32, bets: u32) {
+	for i in 0..bets {
+		let bettor: T::AccountId = account("bettor", i, 0);
+		T::Currency::make_free_balance_be(&bettor, 10000000u32.into());
+		let _ = Tictactoe::<T>::place_side_bet(
+			RawOrigin::Signed(bettor).into(),
+			game_index,
+			true,
+			T::Currency::minimum_balance(),
+		);
+	}
+}
+
+/// Create a game on the largest board where host and joiner proposed different winners.
+/// Returns the joiner.
+fn create_disputed_game<T: Config>() -> T::AccountId {
+	let size = T::MaxBoardSize::get() as u8;
+	let host: T::AccountId = account("host", 0, 0);
+	T::Currency::make_free_balance_be(&host, 10000000u32.into());
+	let _ = Tictactoe::<T>::create_game(
+		RawOrigin::Signed(host.clone()).into(),
+		T::Currency::minimum_balance(),
+		1,
+		size,
+		size,
+		None,
+	);
+	let caller: T::AccountId = whitelisted_caller();
+	T::Currency::make_free_balance_be(&caller, 10000000u32.into());
+	let _ = Tictactoe::<T>::join_game(RawOrigin::Signed(caller.clone()).into(), 0u32);
+	let _ = Tictactoe::<T>::end_game(RawOrigin::Signed(host.clone()).into(), 0u32, host);
+	let _ =
+		Tictactoe::<T>::end_game(RawOrigin::Signed(caller.clone()).into(), 0u32, caller.clone());
+	caller
+}
+
+/// Transcript of `m` moves filling the largest board row by row. Players alternate along rows
+/// and no line is long enough to win, so every move is replayed.
+fn transcript_of<T: Config>(m: u32) -> TranscriptOf<T> {
+	let size = T::MaxBoardSize::get() as u8;
+	let board = T::Rules::new_board(size, size).expect("board size is within bounds; qed");
+	let moves = T::Rules::legal_moves(&board).into_iter().take(m as usize).collect::<Vec<_>>();
+	BoundedVec::try_from(moves).expect("transcript fits in the board; qed")
+}
+
 #[benchmarks]
 mod benchmarks {
 	use super::*;
@@ -34,7 +199,7 @@ mod benchmarks {
 		let game_index: u32 = 0;
 		// Call create_game extrinsic
 		#[extrinsic_call]
-		create_game(RawOrigin::Signed(caller), bet);
+		create_game(RawOrigin::Signed(caller), bet, 1, 3, 3, Some(bet));
 
 		// Check that desired state was set
 		assert_eq!(Tictactoe::<T>::games(game_index).unwrap().bet, bet);
@@ -45,10 +210,14 @@ mod benchmarks {
 		// Create and fund and account for game creation.
 		let host = account("host", 0, 0);
 		T::Currency::make_free_balance_be(&host, BalanceOf::<T>::max_value());
-		// Create a game.
+		// Create a game with a join bonus.
 		let _ = Tictactoe::<T>::create_game(
 			RawOrigin::Signed(host.clone()).into(),
 			T::Currency::minimum_balance(),
+			1,
+			3,
+			3,
+			Some(T::Currency::minimum_balance()),
 		);
 		// Create a joiner account.
 		let caller: T::AccountId = whitelisted_caller();
@@ -62,38 +231,58 @@ mod benchmarks {
 	}
 
 	#[benchmark]
-	fn end_game() {
-		// Fund pallet account for keep alive.
-		T::Currency::make_free_balance_be(&Tictactoe::<T>::account_id(), 1000u32.into());
+	fn end_game_first_proposal() {
+		let (host, _) = create_joined_game::<T>(1000u32.into());
+		let session_key = session_key_of::<T>(&host);
+		// Host proposes itself as the winner through a session key.
+		#[extrinsic_call]
+		end_game(RawOrigin::Signed(session_key), 0u32, host.clone());
 
-		// Create a game instance.
-		let host = account("host", 0, 0);
-		let bet = 1000u32.into();
-		T::Currency::make_free_balance_be(&host, 10000000u32.into());
-		let _ = Tictactoe::<T>::create_game(RawOrigin::Signed(host.clone()).into(), bet);
+		assert_eq!(Tictactoe::<T>::games(0).unwrap().handshake, (Some(host.clone()), None));
+		assert_last_event::<T>(
+			Event::WinnerProposed { game_index: 0, winner: host.clone(), proposer: host }.into(),
+		);
+	}
 
-		// Create a joiner account and join the game.
-		let caller: T::AccountId = whitelisted_caller();
-		T::Currency::make_free_balance_be(&caller, 10000000u32.into());
-		let _ = Tictactoe::<T>::join_game(RawOrigin::Signed(caller.clone()).into(), 0u32);
+	#[benchmark]
+	fn end_game_mismatch() {
+		let (host, joiner) = create_joined_game::<T>(1000u32.into());
+		let _ =
+			Tictactoe::<T>::end_game(RawOrigin::Signed(host.clone()).into(), 0u32, host.clone());
+		let session_key = session_key_of::<T>(&joiner);
+		// Joiner proposes itself as the winner, which requests mediation.
+		#[extrinsic_call]
+		end_game(RawOrigin::Signed(session_key), 0u32, joiner.clone());
 
-		// Check that game is set and ready to be ended.
-		assert!(Tictactoe::<T>::games(0).unwrap().bet == bet);
-		assert_eq!(
-			Tictactoe::<T>::games(0).unwrap().payout_addresses,
-			(Some(host.clone()), Some(caller.clone()))
+		assert_eq!(Tictactoe::<T>::games(0).unwrap().handshake, (Some(host), Some(joiner.clone())));
+		assert_last_event::<T>(
+			Event::MediationRequested { game_index: 0, winner: joiner.clone(), proposer: joiner }
+				.into(),
 		);
+	}
 
-		// Host ends the game.
+	#[benchmark]
+	fn end_game_agreement() {
+		let bet: BalanceOf<T> = 1000u32.into();
+		let (host, joiner) = create_joined_game::<T>(bet);
 		let _ =
 			Tictactoe::<T>::end_game(RawOrigin::Signed(host.clone()).into(), 0u32, host.clone());
+		let session_key = session_key_of::<T>(&joiner);
 		// The longest path is when game is automatically ended, so joiner agrees with host
-		// proposal.
+		// proposal. Jackpot and both safeguard deposits are transferred.
 		#[extrinsic_call]
-		end_game(RawOrigin::Signed(caller.clone()), 0u32, host.clone());
+		end_game(RawOrigin::Signed(session_key), 0u32, host.clone());
 
-		// Check that desired state was set
-		assert_eq!(Tictactoe::<T>::games(0).unwrap().handshake, (Some(host.clone()), Some(host)));
+		assert!(Tictactoe::<T>::games(0).unwrap().ended);
+		assert_last_event::<T>(
+			Event::GameEnded {
+				game_index: 0,
+				winner: host,
+				jackpot: bet.saturating_mul(2u32.into()),
+				resolution: ResolutionKind::Agreement,
+			}
+			.into(),
+		);
 	}
 
 	#[benchmark]
@@ -105,21 +294,27 @@ mod benchmarks {
 	}
 
 	#[benchmark]
-	fn force_end_game() {
-		T::Currency::make_free_balance_be(&Tictactoe::<T>::account_id(), 1000u32.into());
-
-		let deposit_value = 1000u32.into();
-		let _ = Tictactoe::<T>::set_safeguard_deposit(RawOrigin::Root.into(), deposit_value);
+	fn set_game_fee() {
+		let fee = 1000u32.into();
+		#[extrinsic_call]
+		set_game_fee(RawOrigin::Root, fee);
+		assert_eq!(Tictactoe::<T>::game_fee(), fee);
+	}
 
-		let initial_balance = 10000000u32.into();
-		let host = account("host", 0, 0);
-		T::Currency::make_free_balance_be(&host, initial_balance);
-		let joiner = account("joiner", 0, 0);
-		T::Currency::make_free_balance_be(&joiner, initial_balance);
+	#[benchmark]
+	fn set_bet_limits() {
+		let (min_bet, max_bet) = (1000u32.into(), Some(10000u32.into()));
+		#[extrinsic_call]
+		set_bet_limits(RawOrigin::Root, min_bet, max_bet);
+		assert_eq!(Tictactoe::<T>::bet_limits(), (min_bet, max_bet));
+	}
 
-		let bet = 1000u32.into();
-		let _ = Tictactoe::<T>::create_game(RawOrigin::Signed(host.clone()).into(), bet);
-		let _ = Tictactoe::<T>::join_game(RawOrigin::Signed(joiner.clone()).into(), 0u32);
+	#[benchmark]
+	fn force_end_game(n: Linear<0, { T::MaxSideBetsPerGame::get() }>) {
+		let bet: BalanceOf<T> = 1000u32.into();
+		let (host, joiner) = create_joined_game::<T>(bet);
+		// Every side bet backs the winner, so every bettor is paid.
+		place_side_bets::<T>(0u32, n);
 
 		// Force game is intended to be called on disputed game.
 		// Host and joiner propose different winners.
@@ -130,15 +325,77 @@ mod benchmarks {
 			0u32,
 			joiner.clone(),
 		);
+		assert_eq!(
+			Tictactoe::<T>::games(0).unwrap().handshake,
+			(Some(host.clone()), Some(joiner.clone()))
+		);
+		let host_balance = T::Currency::free_balance(&host);
+		let joiner_balance = T::Currency::free_balance(&joiner);
 
-		assert_eq!(Tictactoe::<T>::games(0).unwrap().handshake, (Some(host.clone()), Some(joiner)));
+		// Force end game as root. Host wins but the deposit is returned to the joiner, so the
+		// jackpot and the deposit are transferred to different accounts.
+		#[extrinsic_call]
+		force_end_game(RawOrigin::Root, 0u32, host.clone(), joiner.clone());
 
-		// Force end game as root.
+		// Check that desired state was set, the storage deposit is held until the game is pruned.
+		assert_eq!(
+			T::Currency::free_balance(&host),
+			host_balance + bet.saturating_mul(2u32.into())
+		);
+		assert_eq!(T::Currency::free_balance(&joiner), joiner_balance + bet);
+		assert!(Tictactoe::<T>::side_bets(0).is_empty());
+		assert_last_event::<T>(
+			Event::GameEnded {
+				game_index: 0,
+				winner: host,
+				jackpot: bet.saturating_mul(2u32.into()),
+				resolution: ResolutionKind::Forced,
+			}
+			.into(),
+		);
+	}
+
+	#[benchmark]
+	fn force_end_tournament_game(p: Linear<2, { T::MaxTournamentPlayers::get() }>) {
+		// Fund pallet account for keep alive.
+		T::Currency::make_free_balance_be(
+			&Tictactoe::<T>::account_id(),
+			T::Currency::minimum_balance(),
+		);
+		frame_system::Pallet::<T>::set_block_number(1u32.into());
+		let max_players = bracket_size(p);
+		let players = create_tournament_with_players::<T>(max_players, max_players);
+
+		// Host wins every first round game but the last one, which is left undecided.
+		let last_game = max_players / 2 - 1;
+		for (game_index, pair) in players.chunks_exact(2).enumerate().take(last_game as usize) {
+			for player in pair {
+				let _ = Tictactoe::<T>::end_game(
+					RawOrigin::Signed(player.clone()).into(),
+					game_index as u32,
+					pair[0].clone(),
+				);
+			}
+		}
+		let host = players[2 * last_game as usize].clone();
+
+		// Forcing the last game creates the next round, or pays out prizes when it is the final.
 		#[extrinsic_call]
-		force_end_game(RawOrigin::Root, 0u32, host.clone(), host.clone());
+		force_end_game(RawOrigin::Root, last_game, host.clone(), host.clone());
 
-		// Check that desired state was set
-		assert_eq!(T::Currency::free_balance(&host), initial_balance.saturating_add(bet));
+		assert!(Tictactoe::<T>::games(last_game).unwrap().ended);
+		assert_eq!(
+			Tictactoe::<T>::tournaments(0).unwrap().eliminated.len() as u32,
+			max_players / 2
+		);
+		let event: <T as Config>::RuntimeEvent = Event::GameEnded {
+			game_index: last_game,
+			winner: host,
+			jackpot: Zero::zero(),
+			resolution: ResolutionKind::Forced,
+		}
+		.into();
+		frame_system::Pallet::<T>::assert_has_event(event.into());
 	}
 
 	#[benchmark]
@@ -151,5 +408,378 @@ mod benchmarks {
 		assert_eq!(T::Currency::free_balance(&beneficiary), amount);
 	}
 
+	#[benchmark]
+	fn create_tournament() {
+		let caller: T::AccountId = whitelisted_caller();
+		let positions = T::MaxPrizePositions::get();
+		let prize_distribution: Vec<Permill> =
+			(0..positions).map(|_| Permill::from_rational(1u32, positions)).collect();
+		#[extrinsic_call]
+		create_tournament(
+			RawOrigin::Signed(caller.clone()),
+			T::Currency::minimum_balance(),
+			T::MaxTournamentPlayers::get(),
+			prize_distribution,
+			Permill::from_percent(10),
+		);
+
+		assert_eq!(Tictactoe::<T>::tournaments(0).unwrap().organizer, caller);
+	}
+
+	#[benchmark]
+	fn register_for_tournament(p: Linear<2, { T::MaxTournamentPlayers::get() }>) {
+		let max_players = bracket_size(p);
+		create_tournament_with_players::<T>(max_players, max_players - 1);
+		// The last player fills the bracket and creates every game of the first round.
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, 10000000u32.into());
+		#[extrinsic_call]
+		register_for_tournament(RawOrigin::Signed(caller), 0u32);
+
+		assert_eq!(Tictactoe::<T>::tournaments(0).unwrap().status, TournamentStatus::InProgress);
+		assert_eq!(Tictactoe::<T>::game_index(), max_players / 2);
+	}
+
+	#[benchmark]
+	fn cancel_tournament(p: Linear<2, { T::MaxTournamentPlayers::get() }>) {
+		// Fund pallet account for keep alive.
+		T::Currency::make_free_balance_be(
+			&Tictactoe::<T>::account_id(),
+			T::Currency::minimum_balance(),
+		);
+		// Leave one spot open so the tournament doesn't start, every registered player is
+		// refunded.
+		let max_players = bracket_size(T::MaxTournamentPlayers::get());
+		let registered = (p - 1).min(max_players - 1);
+		create_tournament_with_players::<T>(max_players, registered);
+		let organizer: T::AccountId = account("organizer", 0, 0);
+		#[extrinsic_call]
+		cancel_tournament(RawOrigin::Signed(organizer), 0u32);
+
+		assert_eq!(Tictactoe::<T>::tournaments(0).unwrap().status, TournamentStatus::Cancelled);
+	}
+
+	#[benchmark]
+	fn settle_tournament_game(p: Linear<2, { T::MaxTournamentPlayers::get() }>) {
+		// Fund pallet account for keep alive.
+		T::Currency::make_free_balance_be(
+			&Tictactoe::<T>::account_id(),
+			T::Currency::minimum_balance(),
+		);
+		let max_players = bracket_size(p);
+		let players = create_tournament_with_players::<T>(max_players, max_players);
+
+		// Host wins every first round game, the last one is only proposed.
+		let last_game = max_players / 2 - 1;
+		for (game_index, pair) in players.chunks_exact(2).enumerate() {
+			let game_index = game_index as u32;
+			let _ = Tictactoe::<T>::end_game(
+				RawOrigin::Signed(pair[0].clone()).into(),
+				game_index,
+				pair[0].clone(),
+			);
+			if game_index != last_game {
+				let _ = Tictactoe::<T>::end_game(
+					RawOrigin::Signed(pair[1].clone()).into(),
+					game_index,
+					pair[0].clone(),
+				);
+			}
+		}
+		let host = players[2 * last_game as usize].clone();
+		let joiner = players[2 * last_game as usize + 1].clone();
+
+		// Deciding the last game creates the next round, or pays out prizes when it is the final.
+		#[extrinsic_call]
+		end_game(RawOrigin::Signed(joiner), last_game, host.clone());
+
+		assert!(Tictactoe::<T>::games(last_game).unwrap().ended);
+		assert_eq!(
+			Tictactoe::<T>::tournaments(0).unwrap().eliminated.len() as u32,
+			max_players / 2
+		);
+	}
+
+	#[benchmark]
+	fn place_side_bet() {
+		// The last side bet fills the pool.
+		create_game_with_side_bets::<T>(T::MaxSideBetsPerGame::get() - 1);
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, 10000000u32.into());
+		#[extrinsic_call]
+		place_side_bet(RawOrigin::Signed(caller), 0u32, true, T::Currency::minimum_balance());
+
+		assert_eq!(Tictactoe::<T>::side_bets(0).len() as u32, T::MaxSideBetsPerGame::get());
+	}
+
+	#[benchmark]
+	fn settle_side_bets(n: Linear<1, { T::MaxSideBetsPerGame::get() }>) {
+		// Fund pallet account for keep alive.
+		T::Currency::make_free_balance_be(
+			&Tictactoe::<T>::account_id(),
+			T::Currency::minimum_balance(),
+		);
+		// Every bet backs the winner, so every bettor is paid.
+		create_game_with_side_bets::<T>(n);
+		#[block]
+		{
+			let _ = Tictactoe::<T>::settle_side_bets(0u32, true);
+		}
+
+		assert!(Tictactoe::<T>::side_bets(0).is_empty());
+		assert_eq!(T::Currency::free_balance(&account("bettor", 0, 0)), 10000000u32.into());
+	}
+
+	#[benchmark]
+	fn play_move(s: Linear<3, { T::MaxBoardSize::get() }>) {
+		let size = s as u8;
+		let host: T::AccountId = account("host", 0, 0);
+		T::Currency::make_free_balance_be(&host, 10000000u32.into());
+		let _ = Tictactoe::<T>::create_game(
+			RawOrigin::Signed(host.clone()).into(),
+			T::Currency::minimum_balance(),
+			1,
+			size,
+			size,
+			None,
+		);
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, 10000000u32.into());
+		let _ = Tictactoe::<T>::join_game(RawOrigin::Signed(caller.clone()).into(), 0u32);
+
+		// Fill every row but the last one in order. Players alternate along rows and no line is
+		// long enough to win, so the board is evaluated with as many marks as possible.
+		for turn in 0..s * (s - 1) {
+			let player = if turn % 2 == 0 { host.clone() } else { caller.clone() };
+			let board = Tictactoe::<T>::boards(0)
+				.or_else(|| T::Rules::new_board(size, size))
+				.expect("board size is within bounds; qed");
+			let game_move = T::Rules::legal_moves(&board).remove(0);
+			let _ = Tictactoe::<T>::play_move(RawOrigin::Signed(player).into(), 0u32, game_move);
+		}
+		// Host marks the second cell of the last row, which doesn't complete any line.
+		let board = Tictactoe::<T>::boards(0).unwrap();
+		let game_move = T::Rules::legal_moves(&board).remove(1);
+		#[extrinsic_call]
+		play_move(RawOrigin::Signed(host), 0u32, game_move);
+
+		let board = Tictactoe::<T>::boards(0).unwrap();
+		assert_eq!(T::Rules::moves_played(&board), s * (s - 1) + 1);
+	}
+
+	#[benchmark]
+	fn create_vs_house() {
+		// Fund house pot, the bet is taken from both sides.
+		T::Currency::make_free_balance_be(&Tictactoe::<T>::house_pot_account(), 10000000u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, 10000000u32.into());
+		let bet = T::Currency::minimum_balance();
+		#[extrinsic_call]
+		create_vs_house(RawOrigin::Signed(caller), bet, T::MaxHouseDifficulty::get());
+
+		assert_eq!(
+			Tictactoe::<T>::games(0).unwrap().payout_addresses.1,
+			Some(Tictactoe::<T>::house_pot_account())
+		);
+	}
+
+	#[benchmark]
+	fn fund_house_pot() {
+		T::Currency::make_free_balance_be(&Tictactoe::<T>::account_id(), 100000u32.into());
+		let amount = 1000u32.into();
+		#[extrinsic_call]
+		fund_house_pot(RawOrigin::Root, amount);
+		assert_eq!(T::Currency::free_balance(&Tictactoe::<T>::house_pot_account()), amount);
+	}
+
+	#[benchmark]
+	fn house_move(d: Linear<0, { T::MaxHouseDifficulty::get() as u32 }>) {
+		// The house searches the most moves on a board with a single mark.
+		let mut board = T::Rules::new_board(3, 3).expect("classic board is supported; qed");
+		let opening = T::Rules::legal_moves(&board).remove(0);
+		let _ = T::Rules::apply_move(&mut board, Side::Host, &opening);
+		#[block]
+		{
+			let _ = Tictactoe::<T>::house_move(&board, d as u8);
+		}
+	}
+
+	#[benchmark]
+	fn submit_transcript(m: Linear<0, { T::MaxBoardSize::get() * (T::MaxBoardSize::get() - 1) }>) {
+		let caller = create_disputed_game::<T>();
+		let transcript = transcript_of::<T>(m);
+		#[extrinsic_call]
+		submit_transcript(RawOrigin::Signed(caller), 0u32, transcript);
+
+		assert!(Tictactoe::<T>::transcript_hashes(0).1.is_some());
+	}
+
+	#[benchmark]
+	fn replay_transcript(m: Linear<0, { T::MaxBoardSize::get() * (T::MaxBoardSize::get() - 1) }>) {
+		create_disputed_game::<T>();
+		let game = Tictactoe::<T>::games(0).unwrap();
+		let transcript = transcript_of::<T>(m);
+		#[block]
+		{
+			let _ = Tictactoe::<T>::replay_transcript(&game, &transcript);
+		}
+	}
+
+	#[benchmark]
+	fn claim_timeout() {
+		// Fund pallet account for keep alive.
+		T::Currency::make_free_balance_be(&Tictactoe::<T>::account_id(), 1000u32.into());
+
+		let host: T::AccountId = account("host", 0, 0);
+		T::Currency::make_free_balance_be(&host, 10000000u32.into());
+		let _ = Tictactoe::<T>::create_game(
+			RawOrigin::Signed(host.clone()).into(),
+			T::Currency::minimum_balance(),
+			1,
+			3,
+			3,
+			None,
+		);
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, 10000000u32.into());
+		let _ = Tictactoe::<T>::join_game(RawOrigin::Signed(caller.clone()).into(), 0u32);
+
+		// Host opens the board and the joiner misses the deadline.
+		let board = T::Rules::new_board(3, 3).expect("classic board is supported; qed");
+		let game_move = T::Rules::legal_moves(&board).remove(0);
+		let _ = Tictactoe::<T>::play_move(RawOrigin::Signed(host.clone()).into(), 0u32, game_move);
+		let deadline = Tictactoe::<T>::move_deadlines(0).unwrap();
+		frame_system::Pallet::<T>::set_block_number(deadline + 1u32.into());
+		#[extrinsic_call]
+		claim_timeout(RawOrigin::None, 0u32);
+
+		assert!(Tictactoe::<T>::games(0).unwrap().ended);
+	}
+
+	#[benchmark]
+	fn set_session_key() {
+		let caller: T::AccountId = whitelisted_caller();
+		let session_key: T::AccountId = account("session", 0, 0);
+		#[extrinsic_call]
+		set_session_key(RawOrigin::Signed(caller.clone()), session_key.clone(), Some(0u32));
+
+		assert_eq!(Tictactoe::<T>::session_keys(caller, session_key), Some(Some(0)));
+	}
+
+	#[benchmark]
+	fn remove_session_key() {
+		let caller: T::AccountId = whitelisted_caller();
+		let session_key: T::AccountId = account("session", 0, 0);
+		let _ = Tictactoe::<T>::set_session_key(
+			RawOrigin::Signed(caller.clone()).into(),
+			session_key.clone(),
+			None,
+		);
+		#[extrinsic_call]
+		remove_session_key(RawOrigin::Signed(caller.clone()), session_key.clone());
+
+		assert!(Tictactoe::<T>::session_keys(caller, session_key).is_none());
+	}
+
+	#[benchmark]
+	fn prune_game() {
+		// Fund pallet account for keep alive.
+		T::Currency::make_free_balance_be(&Tictactoe::<T>::account_id(), 1000u32.into());
+
+		let host: T::AccountId = account("host", 0, 0);
+		let joiner: T::AccountId = account("joiner", 0, 0);
+		T::Currency::make_free_balance_be(&host, 10000000u32.into());
+		T::Currency::make_free_balance_be(&joiner, 10000000u32.into());
+		let _ = Tictactoe::<T>::create_game(
+			RawOrigin::Signed(host.clone()).into(),
+			T::Currency::minimum_balance(),
+			1,
+			3,
+			3,
+			None,
+		);
+		let _ = Tictactoe::<T>::join_game(RawOrigin::Signed(joiner).into(), 0u32);
+		let _ = Tictactoe::<T>::force_end_game(RawOrigin::Root.into(), 0u32, host.clone(), host);
+		let caller: T::AccountId = whitelisted_caller();
+		#[extrinsic_call]
+		prune_game(RawOrigin::Signed(caller), 0u32);
+
+		assert!(Tictactoe::<T>::games(0).is_none());
+	}
+
+	#[benchmark]
+	fn force_end_games(n: Linear<1, { T::MaxForceEndBatch::get() }>) {
+		T::Currency::make_free_balance_be(&Tictactoe::<T>::account_id(), 1000u32.into());
+		let _ = Tictactoe::<T>::set_safeguard_deposit(RawOrigin::Root.into(), 1000u32.into());
+
+		// Every entry settles a disputed game between different players.
+		let mut settlements = Vec::new();
+		for i in 0..n {
+			let host: T::AccountId = account("host", i, 0);
+			let joiner: T::AccountId = account("joiner", i, 0);
+			T::Currency::make_free_balance_be(&host, 10000000u32.into());
+			T::Currency::make_free_balance_be(&joiner, 10000000u32.into());
+			let _ = Tictactoe::<T>::create_game(
+				RawOrigin::Signed(host.clone()).into(),
+				1000u32.into(),
+				1,
+				3,
+				3,
+				None,
+			);
+			let _ = Tictactoe::<T>::join_game(RawOrigin::Signed(joiner.clone()).into(), i);
+			let _ =
+				Tictactoe::<T>::end_game(RawOrigin::Signed(host.clone()).into(), i, host.clone());
+			let _ = Tictactoe::<T>::end_game(RawOrigin::Signed(joiner.clone()).into(), i, joiner);
+			settlements.push((i, host.clone(), host));
+		}
+		let settlements = BoundedVec::try_from(settlements).expect("n is bounded; qed");
+		#[extrinsic_call]
+		force_end_games(RawOrigin::Root, settlements);
+
+		assert!((0..n).all(|i| Tictactoe::<T>::games(i).unwrap().ended));
+	}
+
+	#[benchmark]
+	fn offer_rematch() {
+		let caller = create_ended_game::<T>();
+		#[extrinsic_call]
+		offer_rematch(RawOrigin::Signed(caller.clone()), 0u32);
+
+		assert_eq!(Tictactoe::<T>::rematch_offers(0).map(|(player, _)| player), Some(caller));
+	}
+
+	#[benchmark]
+	fn accept_rematch() {
+		let caller = create_ended_game::<T>();
+		let host: T::AccountId = account("host", 0, 0);
+		let _ = Tictactoe::<T>::offer_rematch(RawOrigin::Signed(host).into(), 0u32);
+		#[extrinsic_call]
+		accept_rematch(RawOrigin::Signed(caller), 0u32);
+
+		assert!(Tictactoe::<T>::games(1).is_some());
+	}
+
+	#[benchmark]
+	fn cancel_game() {
+		// Fund pallet account
+		T::Currency::make_free_balance_be(&Tictactoe::<T>::account_id(), 1000u32.into());
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, 10000000u32.into());
+		let bet = T::Currency::minimum_balance();
+		let _ = Tictactoe::<T>::create_game(
+			RawOrigin::Signed(caller.clone()).into(),
+			bet,
+			1,
+			3,
+			3,
+			Some(bet),
+		);
+		#[extrinsic_call]
+		cancel_game(RawOrigin::Signed(caller), 0u32);
+
+		assert!(Tictactoe::<T>::games(0).unwrap().ended);
+	}
+
 	impl_benchmark_test_suite!(Tictactoe, crate::mock::new_test_ext(), crate::mock::Test);
 }
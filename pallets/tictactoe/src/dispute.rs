@@ -0,0 +1,154 @@
+//! Dispute resolution by the offchain worker.
+//!
+//! When the handshake of a game mismatches, both players can submit the transcript of the
+//! disputed round. Only its hash is kept on chain, the transcript itself is written to the offchain
+//! database through offchain indexing, keyed by its hash, and cleared once the round is decided.
+//! If both players submitted the same transcript, the offchain worker replays it with the
+//! configured [`GameRules`] and submits a signed `resolve_dispute` transaction, which checks the
+//! transcript against the stored hashes and replays it again on chain before deciding the round.
+//! The worker records the block it submitted at and waits `RESOLVE_RESEND_BLOCKS` blocks before
+//! submitting again. Disputes that can't be settled this way are left to root.
+//!
+//! Offchain indexing must be enabled on the node, and the offchain worker needs a key of type
+//! [`KEY_TYPE`] in the keystore to sign transactions.
+
+use super::*;
+use frame_support::{pallet_prelude::*, sp_std::prelude::*};
+use frame_system::offchain::{SendSignedTransaction, Signer};
+use sp_runtime::{offchain::storage::StorageValueRef, traits::Hash, KeyTypeId};
+
+/// Key type of the accounts used by the offchain worker to submit `resolve_dispute`.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"tttd");
+
+/// Prefix of the offchain database keys where transcripts are indexed.
+const TRANSCRIPT_PREFIX: &[u8] = b"tictactoe::transcript";
+
+/// Prefix of the offchain local storage keys recording when `resolve_dispute` was submitted.
+const RESOLVE_SENT_PREFIX: &[u8] = b"tictactoe::resolve-sent";
+
+/// Blocks the offchain worker waits for a submitted `resolve_dispute` to be included before
+/// submitting it again.
+const RESOLVE_RESEND_BLOCKS: u32 = 5;
+
+/// Crypto used by the offchain worker to sign transactions.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Identifier of the offchain worker keys, for runtimes using `MultiSignature`.
+	pub struct TictactoeAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for TictactoeAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature>
+		for TictactoeAuthId
+	{
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// Returns the offchain database key where a transcript of a game is indexed, given its hash.
+pub fn transcript_key<H: Encode>(game_index: u32, hash: &H) -> Vec<u8> {
+	(TRANSCRIPT_PREFIX, game_index, hash).encode()
+}
+
+impl<T: Config> Pallet<T> {
+	/// Replay the transcript of a round, the first mover of the round opening the board.
+	/// Returns the winner if the transcript is legal and ends with a won board.
+	pub(crate) fn replay_transcript(
+		game: &Game<BalanceOf<T>, T::AccountId>,
+		transcript: &[MoveOf<T>],
+	) -> Result<T::AccountId, DispatchError> {
+		let host = game.payout_addresses.0.clone().ok_or_else(|| Error::<T>::BadAddress)?;
+		let joiner = game.payout_addresses.1.clone().ok_or_else(|| Error::<T>::BadAddress)?;
+
+		let mut board = T::Rules::new_board(game.board_size, game.win_length)
+			.ok_or_else(|| Error::<T>::InvalidBoardSize)?;
+		let mut side =
+			if game.first_mover() == Some(host.clone()) { Side::Host } else { Side::Joiner };
+		for game_move in transcript {
+			T::Rules::apply_move(&mut board, side, game_move)
+				.map_err(|_| Error::<T>::InvalidTranscript)?;
+			side = side.opponent();
+		}
+
+		let outcome: BoardOutcome = T::Rules::outcome(&board).into();
+		match outcome {
+			BoardOutcome::Won(Side::Host) => Ok(host),
+			BoardOutcome::Won(Side::Joiner) => Ok(joiner),
+			BoardOutcome::InProgress | BoardOutcome::Draw =>
+				Err(Error::<T>::InvalidTranscript.into()),
+		}
+	}
+
+	/// Remove the transcript hashes of a game and clear the transcripts indexed under them.
+	pub(crate) fn clear_transcripts(game_index: u32) {
+		let (host_hash, joiner_hash) = TranscriptHashes::<T>::take(game_index);
+		for hash in host_hash.iter().chain(joiner_hash.iter()) {
+			sp_io::offchain_index::clear(&transcript_key(game_index, hash));
+		}
+	}
+
+	/// Look for disputes where both players submitted the same transcript and submit a
+	/// `resolve_dispute` transaction for each one of them that replays to a winner, unless one
+	/// was submitted less than `RESOLVE_RESEND_BLOCKS` blocks ago.
+	pub(crate) fn resolve_disputes(block_number: T::BlockNumber) {
+		let signer = Signer::<T, T::AuthorityId>::any_account();
+		if !signer.can_sign() {
+			return
+		}
+
+		for (game_index, hashes) in TranscriptHashes::<T>::iter() {
+			let hash = match hashes {
+				(Some(host_hash), Some(joiner_hash)) if host_hash == joiner_hash => host_hash,
+				_ => continue,
+			};
+			let transcript = match StorageValueRef::persistent(&transcript_key(game_index, &hash))
+				.get::<TranscriptOf<T>>()
+			{
+				Ok(Some(transcript)) if T::Hashing::hash_of(&transcript) == hash => transcript,
+				_ => continue,
+			};
+			// Don't spend fees on transcripts that would be rejected on chain.
+			let replays = Self::games(game_index).map_or(false, |game| {
+				!game.ended && Self::replay_transcript(&game, &transcript).is_ok()
+			});
+			if !replays {
+				continue
+			}
+
+			// Lock the dispute until the resend delay has passed, so a pending transaction
+			// isn't submitted again in every block.
+			let sent_key = (RESOLVE_SENT_PREFIX, game_index).encode();
+			let sent = StorageValueRef::persistent(&sent_key).mutate(
+				|last_sent: Result<Option<T::BlockNumber>, _>| match last_sent {
+					Ok(Some(last_sent))
+						if block_number <
+							last_sent.saturating_add(RESOLVE_RESEND_BLOCKS.into()) =>
+						Err(()),
+					_ => Ok(block_number),
+				},
+			);
+			if sent.is_err() {
+				continue
+			}
+
+			let _ = signer.send_signed_transaction(|_account| Call::resolve_dispute {
+				game_index,
+				transcript: transcript.clone(),
+			});
+		}
+	}
+}
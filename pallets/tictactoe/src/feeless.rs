@@ -0,0 +1,169 @@
+//! Fee-free moves.
+//!
+//! Fees of moves played on chain can exceed small bets. [`CheckTictactoeMove`] wraps the signed
+//! extension that charges fees, usually `ChargeTransactionPayment`, and skips it for moves and
+//! settlement calls made by an active player of the referenced game, or one of its session keys.
+//! The call is checked against chain state before waiving the fee, calls that would fail still pay.
+//! Only one fee-free call is allowed per game and block: the first one included in a block is
+//! free and the other calls for the same game in that block pay as usual. Fee-free calls provide a
+//! tag per game and signer, so calls of both players of a game live side by side in the
+//! transaction pool.
+
+use super::*;
+use codec::{Decode, Encode};
+use frame_support::{
+	pallet_prelude::*,
+	sp_std::{fmt, marker::PhantomData},
+	traits::IsSubType,
+};
+use sp_runtime::traits::{DispatchInfoOf, PostDispatchInfoOf, SignedExtension};
+
+/// Signed extension waiving the fees charged by `S` for moves and settlement calls of active
+/// players. It keeps the encoding and identifier of `S`, so wallets sign it as `S`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckTictactoeMove<T, S>(pub S, PhantomData<T>);
+
+impl<T, S> CheckTictactoeMove<T, S> {
+	/// Wrap the signed extension that charges fees.
+	pub fn new(inner: S) -> Self {
+		Self(inner, PhantomData)
+	}
+}
+
+impl<T, S> From<S> for CheckTictactoeMove<T, S> {
+	fn from(inner: S) -> Self {
+		Self::new(inner)
+	}
+}
+
+impl<T, S: fmt::Debug> fmt::Debug for CheckTictactoeMove<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "CheckTictactoeMove({:?})", self.0)
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Returns the game referenced by a call if it can be made without fees by `who`.
+	/// Moves must be legal and played on turn, winners proposed once and transcripts submitted once
+	/// per dispute, and no fee-free call must have been made for the game in the current block.
+	pub(crate) fn feeless_game(who: &T::AccountId, call: &Call<T>) -> Option<u32> {
+		let game_index = match call {
+			Call::play_move { game_index, .. } |
+			Call::end_game { game_index, .. } |
+			Call::submit_transcript { game_index, .. } => *game_index,
+			_ => return None,
+		};
+		let now = frame_system::Pallet::<T>::block_number();
+		if Self::feeless_calls(game_index) == Some(now) {
+			return None
+		}
+
+		// Only active players of the game or their session keys
+		let game = Self::games(game_index).filter(|game| !game.ended)?;
+		let host = game.payout_addresses.0.clone()?;
+		let joiner = game.payout_addresses.1.clone()?;
+		let player = Self::player_of(who.clone(), game_index, &game);
+		let side = if player == host {
+			Side::Host
+		} else if player == joiner {
+			Side::Joiner
+		} else {
+			return None
+		};
+
+		let feeless = match call {
+			Call::play_move { game_move, .. } => {
+				let mut board = Self::boards(game_index)
+					.or_else(|| T::Rules::new_board(game.board_size, game.win_length))?;
+				side == Self::side_on_turn(&game, &board) &&
+					T::Rules::apply_move(&mut board, side, game_move).is_ok()
+			},
+			Call::end_game { winner, .. } => {
+				let proposed =
+					if side == Side::Host { &game.handshake.0 } else { &game.handshake.1 };
+				game.house_difficulty.is_none() &&
+					proposed.is_none() &&
+					(*winner == host || *winner == joiner)
+			},
+			Call::submit_transcript { .. } => {
+				let (host_winner, joiner_winner) = &game.handshake;
+				let (host_hash, joiner_hash) = Self::transcript_hashes(game_index);
+				let submitted = if side == Side::Host { host_hash } else { joiner_hash };
+				host_winner.is_some() &&
+					joiner_winner.is_some() &&
+					host_winner != joiner_winner &&
+					submitted.is_none()
+			},
+			_ => false,
+		};
+		feeless.then_some(game_index)
+	}
+}
+
+impl<T, S> SignedExtension for CheckTictactoeMove<T, S>
+where
+	T: Config + Send + Sync,
+	T::RuntimeCall: IsSubType<Call<T>>,
+	S: SignedExtension<AccountId = T::AccountId, Call = T::RuntimeCall>,
+{
+	const IDENTIFIER: &'static str = S::IDENTIFIER;
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = S::AdditionalSigned;
+	/// `None` if the fee was waived.
+	type Pre = Option<S::Pre>;
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		self.0.additional_signed()
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		match call.is_sub_type().and_then(|call| Pallet::<T>::feeless_game(who, call)) {
+			// Both players can have a fee-free call pending, whichever is included second in a
+			// block falls back to paying in `pre_dispatch`.
+			Some(game_index) => ValidTransaction::with_tag_prefix("TictactoeFeeless")
+				.and_provides((game_index, who))
+				.build(),
+			None => self.0.validate(who, call, info, len),
+		}
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match call.is_sub_type().and_then(|call| Pallet::<T>::feeless_game(who, call)) {
+			Some(game_index) => {
+				let now = frame_system::Pallet::<T>::block_number();
+				FeelessCalls::<T>::insert(game_index, now);
+				Ok(None)
+			},
+			None => Ok(Some(self.0.pre_dispatch(who, call, info, len)?)),
+		}
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		match pre {
+			// Fee was waived, nothing to refund.
+			Some(None) => Ok(()),
+			Some(Some(pre)) => S::post_dispatch(Some(pre), info, post_info, len, result),
+			None => S::post_dispatch(None, info, post_info, len, result),
+		}
+	}
+}
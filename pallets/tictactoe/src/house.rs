@@ -0,0 +1,70 @@
+//! Games against the house.
+//!
+//! A player can play alone against an on-chain opponent. The house joins the game as any other
+//! joiner, staking its bet and safeguard deposit from the house pot account, and replies to every
+//! move of the player in the same call. Its strategy is a deterministic depth-limited minimax over
+//! the configured [`GameRules`], the difficulty being the number of moves it looks ahead.
+
+use super::*;
+use frame_support::{pallet_prelude::*, sp_std::prelude::*};
+
+impl<T: Config> Pallet<T> {
+	/// Returns the house pot account. The house stakes from and is paid to this account.
+	pub fn house_pot_account() -> T::AccountId {
+		T::PalletId::get().into_sub_account_truncating(b"house")
+	}
+
+	/// Account the bet of a new house game against the house exposure of the current block.
+	pub(crate) fn increase_house_exposure(bet: BalanceOf<T>) -> DispatchResult {
+		let now = frame_system::Pallet::<T>::block_number();
+		let (block, exposure) = Self::house_block_exposure();
+		let exposure = if block == now { exposure } else { Zero::zero() };
+		let exposure = exposure.saturating_add(bet);
+		ensure!(exposure <= T::MaxHouseExposurePerBlock::get(), Error::<T>::HouseExposureExceeded);
+		HouseBlockExposure::<T>::put((now, exposure));
+		Ok(())
+	}
+
+	/// Pick the move of the house, which always plays the joiner side.
+	/// Moves are searched in the order given by the rules and the first best move is kept, so the
+	/// choice only depends on the board and the difficulty.
+	pub(crate) fn house_move(board: &BoardOf<T>, difficulty: u8) -> Option<MoveOf<T>> {
+		let mut best: Option<(i32, MoveOf<T>)> = None;
+		for game_move in T::Rules::legal_moves(board) {
+			let mut next = board.clone();
+			if T::Rules::apply_move(&mut next, Side::Joiner, &game_move).is_err() {
+				continue
+			}
+			let score = -Self::negamax(&next, Side::Host, difficulty);
+			if best.as_ref().map_or(true, |(best_score, _)| score > *best_score) {
+				best = Some((score, game_move));
+			}
+		}
+		best.map(|(_, game_move)| game_move)
+	}
+
+	/// Score a board for `side`, which is about to move, looking `depth` moves ahead.
+	/// Wins score higher the sooner they happen, boards that are not decided within the search
+	/// score as a draw.
+	fn negamax(board: &BoardOf<T>, side: Side, depth: u8) -> i32 {
+		let outcome: BoardOutcome = T::Rules::outcome(board).into();
+		match outcome {
+			BoardOutcome::Won(winner) if winner == side => return depth as i32 + 1,
+			BoardOutcome::Won(_) => return -(depth as i32 + 1),
+			BoardOutcome::Draw => return 0,
+			BoardOutcome::InProgress if depth == 0 => return 0,
+			BoardOutcome::InProgress => {},
+		}
+
+		let mut best = None;
+		for game_move in T::Rules::legal_moves(board) {
+			let mut next = board.clone();
+			if T::Rules::apply_move(&mut next, side, &game_move).is_err() {
+				continue
+			}
+			let score = -Self::negamax(&next, side.opponent(), depth - 1);
+			best = Some(best.map_or(score, |best: i32| best.max(score)));
+		}
+		best.unwrap_or(0)
+	}
+}
@@ -12,15 +12,70 @@
 //! “joiner”. Both the host and the joiner must also deposit a safeguard deposit to be slashed in
 //! case of bad behavior.
 //!
-//! Game logic is not tracked on chain so the winner must be stated when finishing a game, to avoid
-//! users closing games in a malicious way, both the host and the joiner must propose a winner.
+//! Game logic can be kept off chain, in which case the winner must be stated when finishing a game,
+//! to avoid users closing games in a malicious way, both the host and the joiner must propose a
+//! winner.
 //! If the proposed winners match, jackpot is sent to that winner and safeguard deposit are released
 //! and game is automatically ended. If the proposed winners do not match, a root account is able to
 //! force-end a game, it is assumed that this root user is a trusted user that can review the game
 //! logic and history and decide who the legitimate winner is.
 //!
+//! Players can also play their moves on chain with “play_move”. The rules of the game are provided
+//! by the runtime through the `GameRules` trait, tic-tac-toe being the default, and as soon as a
+//! board is won the round is decided without waiting for the handshake. Drawn boards are replayed.
+//! Each game sets its board size and win length, such as 5x5 with four in a row, as long as the
+//! rules support it.
+//!
+//! Solo players can play against the house with “create_vs_house”. The house joins the game with
+//! funds from the house pot, which root tops up with “fund_house_pot”, and replies to every move in
+//! the same block with a deterministic minimax strategy. House exposure is capped per game and per
+//! block.
+//!
+//! Disputes can also be settled without root. Once the handshake mismatches, both players can
+//! submit the transcript of the round with “submit_transcript”. If they submitted the same one, an
+//! offchain worker replays it and calls “resolve_dispute”, which checks it on chain and decides the
+//! round.
+//!
+//! While a round is played on chain, the player on turn must move within a timeout. Once it
+//! passes, anyone can submit a fee-free unsigned “claim_timeout” and the opponent takes the round.
+//!
+//! Runtimes can wrap their fee payment signed extension in `CheckTictactoeMove`, which waives the
+//! fee of valid moves and settlement calls made by the players of a game, once per game and block.
+//!
+//! Players can register session keys with “set_session_key”, for a single game or for all their
+//! games, to play moves, propose winners and submit transcripts on their behalf. Funds keep going
+//! through the player account.
+//!
 //! Safeguard deposit can be slashed from the player that proposed the wrong winner, slashed funds
 //! are held in the pallet are can be withdrawn by the sudo user.
+//!
+//! The host also pays a storage deposit for each game, refunded once the ended game is removed with
+//! “prune_game”. At most `MaxOpenGames` games can be waiting to end.
+//!
+//! Hosts can attach a join bonus to a game to attract a joiner. The bonus is escrowed with the bet
+//! and paid to whoever joins, or refunded if the host cancels the game with “cancel_game” before
+//! anyone joins. Games nobody joins within `OpenGameTimeout` blocks expire, anyone can then claim
+//! the expiry with “claim_timeout” and the host is refunded.
+//!
+//! Once a game ends, either player can offer a rematch with “offer_rematch”. If the other player
+//! accepts it with “accept_rematch” before it expires, a new game with the same stake is created
+//! and both stakes are escrowed, the first move being swapped.
+//!
+//! A game can be played as a best-of-N series for a single jackpot by setting a series length when
+//! creating it. Bet and safeguard deposits stay in the pallet across rounds, players agree on the
+//! winner of each round through the same handshake, which is reset between rounds, and the
+//! jackpot is only sent once one of them has won the majority of the rounds. Host and joiner
+//! alternate who moves first each round.
+//!
+//! Games can also be played as part of a single-elimination tournament. An organizer creates a
+//! tournament with “create_tournament” setting an entry fee, the bracket size and how the prize
+//! pool is distributed. Players register with “register_for_tournament” and once the bracket is
+//! full, bracket games are created as regular games. Winners advance automatically each round and
+//! when the final ends the prize pool is paid out.
+//!
+//! Spectators can place side bets on the host or the joiner of a joined game with
+//! “place_side_bet”. Side bets form a parimutuel pool held by the pallet, when the game ends the
+//! pool is shared among the backers of the winner in proportion to their stake.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -33,6 +88,16 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+mod dispute;
+mod feeless;
+mod house;
+pub mod rules;
+mod session_keys;
+mod side_bets;
+mod simulate;
+mod timeout;
+mod tournament;
+
 use codec::{Decode, Encode, MaxEncodedLen};
 use scale_info::TypeInfo;
 
@@ -40,20 +105,32 @@ use frame_support::{
 	dispatch::DispatchResult,
 	ensure,
 	sp_runtime::{
-		traits::{AccountIdConversion, Saturating, Zero},
-		DispatchError,
+		traits::{AccountIdConversion, Hash, Saturating, Zero},
+		DispatchError, Permill,
 	},
+	sp_std::prelude::*,
+	storage::with_storage_layer,
 	traits::{Currency, ExistenceRequirement::KeepAlive, Get},
-	PalletId, RuntimeDebug,
+	weights::Weight,
+	BoundedVec, PalletId, RuntimeDebug,
 };
 
+pub use dispute::{crypto, transcript_key, KEY_TYPE};
+pub use feeless::CheckTictactoeMove;
 pub use pallet::*;
+pub use rules::{BoardOutcome, Cells, GameRules, KInARow, Side, TicTacToe};
+pub use side_bets::SideBet;
+pub use simulate::EndGameOutcome;
+pub use tournament::{Tournament, TournamentStatus};
 
 pub mod weights;
 pub use weights::*;
 
 type BalanceOf<T> =
 	<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+type BoardOf<T> = <<T as Config>::Rules as GameRules>::Board;
+type MoveOf<T> = <<T as Config>::Rules as GameRules>::Move;
+type TranscriptOf<T> = BoundedVec<MoveOf<T>, Cells<<T as Config>::MaxBoardSize>>;
 
 #[derive(
 	Clone, Encode, Decode, Default, PartialEq, Copy, RuntimeDebug, TypeInfo, MaxEncodedLen,
@@ -69,6 +146,150 @@ pub struct Game<Balance, AccountId> {
 	ended: bool,
 	// Stores the handshake between host and joiner to agree on the winner.
 	handshake: (Option<AccountId>, Option<AccountId>),
+	// Index of the tournament this game belongs to, if it is a bracket game.
+	tournament: Option<u32>,
+	// Number of rounds of the series, the jackpot goes to the first player to win the majority.
+	series_length: u32,
+	// Stores the rounds won by the host and joiner.
+	round_wins: (u32, u32),
+	// Cells per side of the board played on chain.
+	board_size: u8,
+	// Marks in a row needed to win a board.
+	win_length: u8,
+	// Search depth of the house strategy if the game is played against the house.
+	house_difficulty: Option<u8>,
+	// Storage deposit paid by the host, refunded when the game is pruned.
+	storage_deposit: Balance,
+	// Bonus escrowed by the host and paid to the joiner when joining the game.
+	join_bonus: Balance,
+}
+
+impl<Balance, AccountId: Clone> Game<Balance, AccountId> {
+	/// Returns the player that moves first in the current round.
+	/// Host moves first in the opening round and players alternate afterwards.
+	pub fn first_mover(&self) -> Option<AccountId> {
+		let rounds_played = self.round_wins.0.saturating_add(self.round_wins.1);
+		if rounds_played % 2 == 0 {
+			self.payout_addresses.0.clone()
+		} else {
+			self.payout_addresses.1.clone()
+		}
+	}
+
+	/// Returns the number of rounds a player must win to take the series.
+	pub fn rounds_to_win(&self) -> u32 {
+		self.series_length / 2 + 1
+	}
+
+	/// Returns the host and joiner of the game.
+	pub fn payout_addresses(&self) -> &(Option<AccountId>, Option<AccountId>) {
+		&self.payout_addresses
+	}
+
+	/// Returns the winners proposed by the host and joiner.
+	pub fn handshake(&self) -> &(Option<AccountId>, Option<AccountId>) {
+		&self.handshake
+	}
+
+	/// Returns the tournament of the game, if it is a bracket game.
+	pub fn tournament(&self) -> Option<u32> {
+		self.tournament
+	}
+
+	/// Returns the number of rounds of the series.
+	pub fn series_length(&self) -> u32 {
+		self.series_length
+	}
+
+	/// Returns the rounds won by the host and joiner.
+	pub fn round_wins(&self) -> (u32, u32) {
+		self.round_wins
+	}
+
+	/// Returns the board size and win length of the game.
+	pub fn board(&self) -> (u8, u8) {
+		(self.board_size, self.win_length)
+	}
+
+	/// Returns the search depth of the house, if the game is played against the house.
+	pub fn house_difficulty(&self) -> Option<u8> {
+		self.house_difficulty
+	}
+}
+
+impl<Balance: Copy + Zero + Saturating + From<u32>, AccountId: PartialEq> Game<Balance, AccountId> {
+	/// Returns the bet each player stakes.
+	pub fn bet(&self) -> Balance {
+		self.bet
+	}
+
+	/// Returns the storage deposit paid by the host.
+	pub fn storage_deposit(&self) -> Balance {
+		self.storage_deposit
+	}
+
+	/// Returns the bonus paid to the joiner.
+	pub fn join_bonus(&self) -> Balance {
+		self.join_bonus
+	}
+
+	/// Returns the status of the game.
+	pub fn status(&self) -> GameStatus {
+		match &self.handshake {
+			_ if self.ended => GameStatus::Ended,
+			(Some(host_winner), Some(joiner_winner)) if host_winner != joiner_winner =>
+				GameStatus::InMediation,
+			_ => GameStatus::Open,
+		}
+	}
+
+	/// Returns the funds the pallet account holds for the game, given the safeguard deposit.
+	/// Ended games only hold their storage deposit until they are pruned.
+	pub fn held_funds(&self, safeguard_deposit: Balance) -> Balance {
+		if self.ended {
+			return self.storage_deposit
+		}
+		// Bracket games are paid by the tournament prize pool.
+		let stake = if self.tournament.is_some() {
+			Zero::zero()
+		} else {
+			self.bet.saturating_add(safeguard_deposit)
+		};
+		let (players, join_bonus) = match self.payout_addresses.1 {
+			Some(_) => (2, Zero::zero()),
+			None => (1, self.join_bonus),
+		};
+		stake
+			.saturating_mul(players.into())
+			.saturating_add(join_bonus)
+			.saturating_add(self.storage_deposit)
+	}
+}
+
+/// Status of a game.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, Copy, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum GameStatus {
+	/// The game is being played or waiting for a joiner.
+	Open,
+	/// Players proposed different winners, the game waits for a dispute resolution or root.
+	InMediation,
+	/// The game has ended.
+	Ended,
+}
+
+/// How the winner of a round or game has been decided.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, Copy, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum ResolutionKind {
+	/// Both players agreed on the winner through `end_game`.
+	Agreement,
+	/// The board has been won on chain.
+	Board,
+	/// The transcript of both players has been replayed through `resolve_dispute`.
+	Dispute,
+	/// The player on turn missed the move deadline.
+	Timeout,
+	/// Root forced the end of the game through `force_end_game`.
+	Forced,
 }
 
 #[frame_support::pallet]
@@ -76,13 +297,17 @@ pub mod pallet {
 
 	use super::*;
 	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
+	use frame_system::{
+		offchain::{AppCrypto, CreateSignedTransaction},
+		pallet_prelude::*,
+	};
+	use sp_runtime::traits::Hash;
 
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: CreateSignedTransaction<Call<Self>> + frame_system::Config {
 		/// Jackpot Pallet Id
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -94,25 +319,170 @@ pub mod pallet {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
 		type WeightInfo: WeightInfo;
+
+		/// Maximum number of players in a tournament bracket.
+		#[pallet::constant]
+		type MaxTournamentPlayers: Get<u32>;
+
+		/// Maximum number of paid standings in a tournament prize distribution.
+		#[pallet::constant]
+		type MaxPrizePositions: Get<u32>;
+
+		/// Maximum number of rounds in a best-of-N series.
+		#[pallet::constant]
+		type MaxSeriesLength: Get<u32>;
+
+		/// Maximum number of side bets placed on a single game.
+		#[pallet::constant]
+		type MaxSideBetsPerGame: Get<u32>;
+
+		/// Maximum number of cells per side of a board.
+		#[pallet::constant]
+		type MaxBoardSize: Get<u32>;
+
+		/// Rules of the game played on chain.
+		type Rules: GameRules;
+
+		/// Maximum bet the house takes on a single game.
+		#[pallet::constant]
+		type MaxHouseBet: Get<BalanceOf<Self>>;
+
+		/// Maximum sum of bets the house takes on games created in the same block.
+		#[pallet::constant]
+		type MaxHouseExposurePerBlock: Get<BalanceOf<Self>>;
+
+		/// Maximum number of moves the house looks ahead.
+		#[pallet::constant]
+		type MaxHouseDifficulty: Get<u8>;
+
+		/// Storage deposit charged per byte of a game, refunded when the game is pruned.
+		#[pallet::constant]
+		type GameDepositPerByte: Get<BalanceOf<Self>>;
+
+		/// Maximum number of games that haven't ended.
+		#[pallet::constant]
+		type MaxOpenGames: Get<u32>;
+
+		/// Maximum number of games force ended in a single batch.
+		#[pallet::constant]
+		type MaxForceEndBatch: Get<u32>;
+
+		/// Number of blocks a rematch offer can be accepted for.
+		#[pallet::constant]
+		type RematchOfferTimeout: Get<Self::BlockNumber>;
+
+		/// Identifier of the keys used by the offchain worker to submit `resolve_dispute`.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// Number of blocks the player on turn has to move once a round is played on chain.
+		#[pallet::constant]
+		type MoveTimeout: Get<Self::BlockNumber>;
+
+		/// Number of blocks a game can wait for a joiner before it expires.
+		#[pallet::constant]
+		type OpenGameTimeout: Get<Self::BlockNumber>;
+
+		/// Base priority of unsigned timeout claims.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
 		/// A game has been created.
-		GameCreated { game_index: u32 },
-		/// A player has joined a game.
-		PlayerJoined { game_index: u32, player: T::AccountId },
+		GameCreated {
+			game_index: u32,
+			host: T::AccountId,
+			bet: BalanceOf<T>,
+			series_length: u32,
+			board_size: u8,
+			win_length: u8,
+			join_bonus: BalanceOf<T>,
+		},
+		/// A player has joined a game and has been paid its join bonus.
+		PlayerJoined {
+			game_index: u32,
+			player: T::AccountId,
+			bet: BalanceOf<T>,
+			join_bonus: BalanceOf<T>,
+		},
+		/// The host has cancelled a game nobody joined, its stake has been refunded.
+		GameCancelled { game_index: u32, refund: BalanceOf<T> },
+		/// Nobody joined a game before its deadline, its stake has been refunded to the host.
+		GameExpired { game_index: u32, refund: BalanceOf<T> },
 		/// A game has ended.
-		GameEnded { game_index: u32, winner: T::AccountId, jackpot: BalanceOf<T> },
+		GameEnded {
+			game_index: u32,
+			winner: T::AccountId,
+			jackpot: BalanceOf<T>,
+			resolution: ResolutionKind,
+		},
 		/// A safeguard deposit has been set.
 		SafeguardDepositSet { deposit: BalanceOf<T> },
+		/// A game fee has been set.
+		GameFeeSet { fee: BalanceOf<T> },
+		/// Bet limits have been set.
+		BetLimitsSet { min_bet: BalanceOf<T>, max_bet: Option<BalanceOf<T>> },
 		/// A winner has been proposed.
 		WinnerProposed { game_index: u32, winner: T::AccountId, proposer: T::AccountId },
 		/// Mediation has been requested.
-		MediationRequested { game_index: u32, proposer: T::AccountId },
+		MediationRequested { game_index: u32, winner: T::AccountId, proposer: T::AccountId },
+		/// A round has been decided and the series goes on.
+		RoundWon {
+			game_index: u32,
+			winner: T::AccountId,
+			round_wins: (u32, u32),
+			resolution: ResolutionKind,
+		},
 		/// Funds has been withdrawn.
 		FundsWithdrawn { amount: BalanceOf<T>, beneficiary: T::AccountId },
+		/// A tournament has been created.
+		TournamentCreated { tournament_index: u32, organizer: T::AccountId },
+		/// A player has registered in a tournament.
+		TournamentPlayerRegistered { tournament_index: u32, player: T::AccountId },
+		/// A new round of bracket games has been created.
+		TournamentRoundStarted { tournament_index: u32, round: u32 },
+		/// The final of a tournament has ended and the prize pool has been paid out.
+		TournamentEnded { tournament_index: u32, champion: T::AccountId, prize_pool: BalanceOf<T> },
+		/// A tournament has been cancelled and entry fees refunded.
+		TournamentCancelled { tournament_index: u32 },
+		/// A spectator has placed a side bet on a game.
+		SideBetPlaced { game_index: u32, bettor: T::AccountId, on_host: bool, amount: BalanceOf<T> },
+		/// The side bet pool of a game has been shared among the backers of the winner.
+		SideBetsSettled { game_index: u32, host_won: bool, total_pool: BalanceOf<T> },
+		/// Side bets of a game have been refunded.
+		SideBetsRefunded { game_index: u32 },
+		/// A move has been played on chain.
+		MovePlayed { game_index: u32, player: T::AccountId, game_move: MoveOf<T> },
+		/// The board ended in a draw and has been cleared to replay the round.
+		RoundDrawn { game_index: u32 },
+		/// The house pot has been funded from the pallet account.
+		HousePotFunded { amount: BalanceOf<T> },
+		/// A player has submitted the transcript of a disputed round.
+		TranscriptSubmitted { game_index: u32, player: T::AccountId },
+		/// A disputed round has been decided by replaying the transcript of both players.
+		DisputeResolved { game_index: u32, winner: T::AccountId },
+		/// The player on turn missed the move deadline and the opponent takes the round.
+		TimeoutClaimed { game_index: u32, winner: T::AccountId },
+		/// A player has registered a session key, for a single game or for all of them.
+		SessionKeySet { player: T::AccountId, session_key: T::AccountId, game_index: Option<u32> },
+		/// A player has removed a session key.
+		SessionKeyRemoved { player: T::AccountId, session_key: T::AccountId },
+		/// An ended game has been removed from storage and its storage deposit refunded.
+		GamePruned { game_index: u32, storage_deposit: BalanceOf<T> },
+		/// A player has offered a rematch of an ended game.
+		RematchOffered { game_index: u32, player: T::AccountId, expires_at: T::BlockNumber },
+		/// A rematch offer has been accepted and the rematch has been created.
+		RematchAccepted { game_index: u32, rematch_index: u32 },
+		/// A game of a batch has been force ended.
+		ForceEndSucceeded { game_index: u32 },
+		/// A game of a batch could not be force ended, its changes have been reverted.
+		ForceEndFailed { game_index: u32, error: DispatchError },
+		/// A safeguard deposit has been returned to a player of an ended game.
+		DepositReturned { game_index: u32, player: T::AccountId, amount: BalanceOf<T> },
+		/// A player has forfeited its safeguard deposit on a game ended by root.
+		DepositSlashed { game_index: u32, player: T::AccountId, amount: BalanceOf<T> },
 	}
 
 	#[pallet::error]
@@ -133,6 +503,78 @@ pub mod pallet {
 		BadAddress,
 		/// Handshale already set
 		HandshakeAlreadySet,
+		/// The tournament does not exist.
+		TournamentDoesNotExist,
+		/// Bracket size must be a power of two between 2 and `MaxTournamentPlayers`.
+		InvalidTournamentSize,
+		/// Prize distribution is empty, too long or hands out more than the prize pool.
+		InvalidPrizeDistribution,
+		/// The tournament is not open for registration.
+		TournamentNotRegistering,
+		/// The account is already registered in the tournament.
+		AlreadyRegistered,
+		/// The tournament bracket is full.
+		TournamentFull,
+		/// The account is not the organizer of the tournament.
+		NotTheOrganizer,
+		/// Series length must be odd and no greater than `MaxSeriesLength`.
+		InvalidSeriesLength,
+		/// The game has not been joined yet.
+		GameNotJoined,
+		/// Players can't place side bets on their own game.
+		PlayersCantSideBet,
+		/// A winner has already been proposed, side bets are closed.
+		SideBetsClosed,
+		/// The maximum number of side bets for the game has been reached.
+		TooManySideBets,
+		/// It is the other player's turn.
+		NotYourTurn,
+		/// The move is not allowed by the game rules.
+		InvalidMove,
+		/// Board size must be no greater than `MaxBoardSize` and supported by the rules.
+		InvalidBoardSize,
+		/// Difficulty must be no greater than `MaxHouseDifficulty`.
+		InvalidDifficulty,
+		/// The bet exceeds what the house takes on a game or in the current block.
+		HouseExposureExceeded,
+		/// Games against the house are only decided on chain.
+		HouseGameIsPlayedOnChain,
+		/// The players of the game have not proposed different winners.
+		NotInMediation,
+		/// The player has already submitted a transcript for the dispute.
+		TranscriptAlreadySubmitted,
+		/// The transcript doesn't match the ones submitted by both players.
+		TranscriptMismatch,
+		/// The transcript is not legal or doesn't end with a won board.
+		InvalidTranscript,
+		/// The game has no move deadline or it has not passed yet.
+		NotTimedOut,
+		/// A player can't register its own account as session key.
+		InvalidSessionKey,
+		/// The session key is not registered by the caller.
+		NotTheSessionKeyOwner,
+		/// `MaxOpenGames` games haven't ended yet.
+		TooManyOpenGames,
+		/// The game has not ended yet.
+		GameNotEnded,
+		/// Tournament and house games can't be rematched.
+		RematchNotAllowed,
+		/// A rematch of the game has already been offered and the offer hasn't expired.
+		RematchAlreadyOffered,
+		/// No rematch of the game has been offered.
+		NoRematchOffer,
+		/// The rematch offer has expired.
+		RematchOfferExpired,
+		/// The rematch offer must be accepted by the other player.
+		OwnRematchOffer,
+		/// The bet is out of the bet limits.
+		BetOutOfLimits,
+		/// The minimum bet is greater than the maximum bet.
+		InvalidBetLimits,
+		/// A winner has been proposed, the round is settled through the handshake.
+		WinnerProposed,
+		/// Nobody joined the game before its join deadline, it can only be expired.
+		JoinDeadlinePassed,
 	}
 
 	/// Index to identify each game.
@@ -140,32 +582,246 @@ pub mod pallet {
 	#[pallet::getter(fn game_index)]
 	pub(crate) type GameIndex<T> = StorageValue<_, u32, ValueQuery>;
 
+	/// Number of games that haven't ended.
+	#[pallet::storage]
+	#[pallet::getter(fn open_games)]
+	pub(crate) type OpenGames<T> = StorageValue<_, u32, ValueQuery>;
+
 	/// Safeguard deposit value to be used in case of dispute.
 	#[pallet::storage]
 	#[pallet::getter(fn safeguard_deposit)]
 	pub(crate) type SafeguardDeposit<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+	/// Fee paid by the host to the pallet account for each game created.
+	#[pallet::storage]
+	#[pallet::getter(fn game_fee)]
+	pub(crate) type GameFee<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// Minimum and maximum, if any, bet of created games.
+	#[pallet::storage]
+	#[pallet::getter(fn bet_limits)]
+	pub(crate) type BetLimits<T> =
+		StorageValue<_, (BalanceOf<T>, Option<BalanceOf<T>>), ValueQuery>;
+
 	/// Storage for game instances.
 	#[pallet::storage]
 	#[pallet::getter(fn games)]
 	pub(crate) type Games<T: Config> =
 		StorageMap<_, Twox64Concat, u32, Game<BalanceOf<T>, T::AccountId>, OptionQuery>;
 
+	/// Index to identify each tournament.
+	#[pallet::storage]
+	#[pallet::getter(fn tournament_index)]
+	pub(crate) type TournamentIndex<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Storage for tournament instances.
+	#[pallet::storage]
+	#[pallet::getter(fn tournaments)]
+	pub(crate) type Tournaments<T: Config> =
+		StorageMap<_, Twox64Concat, u32, Tournament<T>, OptionQuery>;
+
+	/// Side bets placed on each game, settled when the game ends.
+	#[pallet::storage]
+	#[pallet::getter(fn side_bets)]
+	pub(crate) type SideBets<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		BoundedVec<SideBet<BalanceOf<T>, T::AccountId>, T::MaxSideBetsPerGame>,
+		ValueQuery,
+	>;
+
+	/// Boards of the rounds being played on chain.
+	#[pallet::storage]
+	#[pallet::getter(fn boards)]
+	pub(crate) type Boards<T: Config> = StorageMap<_, Twox64Concat, u32, BoardOf<T>, OptionQuery>;
+
+	/// Block number and sum of the bets taken by the house in that block.
+	#[pallet::storage]
+	#[pallet::getter(fn house_block_exposure)]
+	pub(crate) type HouseBlockExposure<T: Config> =
+		StorageValue<_, (T::BlockNumber, BalanceOf<T>), ValueQuery>;
+
+	/// Hashes of the transcripts submitted by the host and joiner of a disputed game.
+	/// Transcripts themselves are only kept in the offchain database.
+	#[pallet::storage]
+	#[pallet::getter(fn transcript_hashes)]
+	pub(crate) type TranscriptHashes<T: Config> =
+		StorageMap<_, Twox64Concat, u32, (Option<T::Hash>, Option<T::Hash>), ValueQuery>;
+
+	/// Block by which the player on turn must move, for games played on chain, or by which an open
+	/// game must be joined.
+	#[pallet::storage]
+	#[pallet::getter(fn move_deadlines)]
+	pub(crate) type MoveDeadlines<T: Config> =
+		StorageMap<_, Twox64Concat, u32, T::BlockNumber, OptionQuery>;
+
+	/// Last block a fee-free call was made for each game.
+	#[pallet::storage]
+	#[pallet::getter(fn feeless_calls)]
+	pub(crate) type FeelessCalls<T: Config> =
+		StorageMap<_, Twox64Concat, u32, T::BlockNumber, OptionQuery>;
+
+	/// Session keys registered by each player and the game, or all games if `None`, they act in.
+	#[pallet::storage]
+	#[pallet::getter(fn session_keys)]
+	pub(crate) type SessionKeys<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		T::AccountId,
+		Option<u32>,
+		OptionQuery,
+	>;
+
+	/// Player that offered a rematch of each ended game and the last block it can be accepted.
+	#[pallet::storage]
+	#[pallet::getter(fn rematch_offers)]
+	pub(crate) type RematchOffers<T: Config> =
+		StorageMap<_, Blake2_128Concat, u32, (T::AccountId, T::BlockNumber), OptionQuery>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Initial safeguard deposit.
+		pub safeguard_deposit: BalanceOf<T>,
+		/// Initial game fee.
+		pub game_fee: BalanceOf<T>,
+		/// Initial minimum and maximum, if any, bet.
+		pub bet_limits: (BalanceOf<T>, Option<BalanceOf<T>>),
+		/// Demo games to create, as host, joiner if the game is joined, and bet.
+		pub demo_games: Vec<(T::AccountId, Option<T::AccountId>, BalanceOf<T>)>,
+		/// Indices of the joined demo games sent to mediation, each player proposes itself as
+		/// winner.
+		pub disputed_demo_games: Vec<u32>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self {
+				safeguard_deposit: Zero::zero(),
+				game_fee: Zero::zero(),
+				bet_limits: (Zero::zero(), None),
+				demo_games: Vec::new(),
+				disputed_demo_games: Vec::new(),
+			}
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			let (min_bet, max_bet) = self.bet_limits;
+			assert!(max_bet.map_or(true, |max_bet| min_bet <= max_bet), "Invalid bet limits");
+			SafeguardDeposit::<T>::put(self.safeguard_deposit);
+			GameFee::<T>::put(self.game_fee);
+			BetLimits::<T>::put(self.bet_limits);
+
+			// Demo games are created and joined as regular games, hosts and joiners must be
+			// endowed.
+			for (host, joiner, bet) in &self.demo_games {
+				let game_index = Pallet::<T>::game_index();
+				Pallet::<T>::create_game(
+					frame_system::RawOrigin::Signed(host.clone()).into(),
+					*bet,
+					1,
+					3,
+					3,
+					None,
+				)
+				.expect("Demo game host can't create the game");
+				if let Some(joiner) = joiner {
+					Pallet::<T>::join_game(
+						frame_system::RawOrigin::Signed(joiner.clone()).into(),
+						game_index,
+					)
+					.expect("Demo game joiner can't join the game");
+				}
+			}
+			for game_index in &self.disputed_demo_games {
+				let game = Pallet::<T>::games(game_index).expect("Disputed demo game must exist");
+				let (host, joiner) = game.payout_addresses;
+				for player in [host, joiner] {
+					let player = player.expect("Disputed demo game must be joined");
+					Pallet::<T>::end_game(
+						frame_system::RawOrigin::Signed(player.clone()).into(),
+						*game_index,
+						player,
+					)
+					.expect("Disputed demo game player can't propose a winner");
+				}
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		/// Resolve disputes where both players submitted the same transcript.
+		fn offchain_worker(block_number: BlockNumberFor<T>) {
+			Self::resolve_disputes(block_number);
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config> ValidateUnsigned for Pallet<T> {
+		type Call = Call<T>;
+
+		/// Only timeout claims can be submitted unsigned, once the move deadline has passed.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			match call {
+				Call::claim_timeout { game_index } => Self::validate_timeout_claim(*game_index),
+				_ => InvalidTransaction::Call.into(),
+			}
+		}
+	}
+
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Create a new game.
 		/// The caller will be the host of the game.
 		/// The bet amount will set the value to other user to join the game.
-		/// Bet amount and safeguard deposit will be transferred to the pallet account.
+		/// Bet amount and safeguard deposit will be transferred to the pallet account, along with
+		/// the game fee. The bet must be within the bet limits.
+		/// Series length sets the number of rounds played for the jackpot, 1 for a single game.
+		/// Board size and win length set the board played on chain, 3 and 3 for classic
+		/// tic-tac-toe.
+		/// An optional join bonus is escrowed along with the bet and paid to the joiner.
+		/// The game expires if nobody joins it within `OpenGameTimeout` blocks.
 		#[pallet::call_index(0)]
 		#[pallet::weight(T::WeightInfo::create_game())]
-		pub fn create_game(origin: OriginFor<T>, bet: BalanceOf<T>) -> DispatchResult {
+		pub fn create_game(
+			origin: OriginFor<T>,
+			bet: BalanceOf<T>,
+			series_length: u32,
+			board_size: u8,
+			win_length: u8,
+			join_bonus: Option<BalanceOf<T>>,
+		) -> DispatchResult {
 			let caller = ensure_signed(origin.clone())?;
 			ensure!(!bet.is_zero(), Error::<T>::CantBeZero);
+			Self::ensure_bet_within_limits(bet)?;
+			ensure!(
+				series_length % 2 == 1 && series_length <= T::MaxSeriesLength::get(),
+				Error::<T>::InvalidSeriesLength
+			);
+			ensure!(
+				board_size as u32 <= T::MaxBoardSize::get() &&
+					T::Rules::new_board(board_size, win_length).is_some(),
+				Error::<T>::InvalidBoardSize
+			);
 
-			// Transfer bet amount and safeguard deposit to pallet account to ensure creator account
-			// has enough funds.
-			let transfer_amount = bet.saturating_add(Self::safeguard_deposit());
+			Self::open_game()?;
+
+			// Transfer bet amount, safeguard deposit, storage deposit, join bonus and game fee to
+			// pallet account to ensure creator account has enough funds.
+			let storage_deposit = Self::game_storage_deposit();
+			let join_bonus = join_bonus.unwrap_or_else(Zero::zero);
+			let transfer_amount = bet
+				.saturating_add(Self::safeguard_deposit())
+				.saturating_add(storage_deposit)
+				.saturating_add(join_bonus)
+				.saturating_add(Self::game_fee());
 			T::Currency::transfer(&caller, &Self::account_id(), transfer_amount, KeepAlive)?;
 
 			// Create new game and write to storage
@@ -175,37 +831,73 @@ pub mod pallet {
 				payout_addresses: (Some(caller.clone()), None),
 				ended: false,
 				handshake: (None, None),
+				tournament: None,
+				series_length,
+				round_wins: (0, 0),
+				board_size,
+				win_length,
+				house_difficulty: None,
+				storage_deposit,
+				join_bonus,
 			};
 			let new_game_index =
 				game_index.checked_add(1).ok_or_else(|| Error::<T>::IndexOverflow)?;
 			Games::<T>::insert(game_index, game);
 			GameIndex::<T>::put(new_game_index);
-			Self::deposit_event(Event::GameCreated { game_index });
+			Self::set_join_deadline(game_index);
+			Self::deposit_game_event(
+				game_index,
+				Event::GameCreated {
+					game_index,
+					host: caller,
+					bet,
+					series_length,
+					board_size,
+					win_length,
+					join_bonus,
+				},
+			);
 			Ok(())
 		}
 
 		/// Join a game by it's index.
 		/// The caller will be the joiner of the game.
 		/// The alredy set bet and safeguard deposit amount will be transferred to the pallet
-		/// account.
+		/// account and the join bonus of the game, if any, paid to the caller.
+		/// Games can't be joined once their join deadline has passed.
 		#[pallet::call_index(1)]
 		#[pallet::weight(T::WeightInfo::join_game())]
 		pub fn join_game(origin: OriginFor<T>, game_index: u32) -> DispatchResult {
 			let caller = ensure_signed(origin.clone())?;
 
 			// Retrieve game and update payout address if joiner account has enough funds.
-			Games::<T>::try_mutate(game_index, |game| -> DispatchResult {
-				let mut game = game.as_mut().ok_or_else(|| Error::<T>::GameDoesNotExist)?;
-				ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
-				ensure!(game.payout_addresses.1 == None, Error::<T>::GameFull);
-				let bet = game.bet;
-				let transfer_amount = bet.saturating_add(Self::safeguard_deposit());
-				T::Currency::transfer(&caller, &Self::account_id(), transfer_amount, KeepAlive)?;
-				game.payout_addresses.1 = Some(caller.clone());
-				Ok(())
-			})?;
+			let (bet, join_bonus) =
+				Games::<T>::try_mutate(game_index, |game| -> Result<_, DispatchError> {
+					let mut game = game.as_mut().ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+					ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
+					ensure!(game.payout_addresses.1 == None, Error::<T>::GameFull);
+					ensure!(
+						!Self::move_deadline_passed(game_index),
+						Error::<T>::JoinDeadlinePassed
+					);
+					let bet = game.bet;
+					let transfer_amount = bet.saturating_add(Self::safeguard_deposit());
+					T::Currency::transfer(
+						&caller,
+						&Self::account_id(),
+						transfer_amount,
+						KeepAlive,
+					)?;
+					Self::transfer_from_pallet(caller.clone(), game.join_bonus)?;
+					game.payout_addresses.1 = Some(caller.clone());
+					MoveDeadlines::<T>::remove(game_index);
+					Ok((bet, game.join_bonus))
+				})?;
 
-			Self::deposit_event(Event::PlayerJoined { game_index, player: caller });
+			Self::deposit_game_event(
+				game_index,
+				Event::PlayerJoined { game_index, player: caller, bet, join_bonus },
+			);
 			Ok(())
 		}
 
@@ -214,13 +906,18 @@ pub mod pallet {
 		/// Expected to be called by the two players of the game.
 		/// Each caller proposes a winner. If they match jackpot is sent, otherwise mediation is
 		/// requested.
+		/// On a series, the agreed winner takes the round and the handshake is reset for the next
+		/// one until a player wins the majority of the rounds.
+		/// Proposing a winner or requesting mediation refunds the weight of deciding the game.
+		/// Once a winner is proposed the move deadline is removed, the round is settled through the
+		/// handshake and can't be timed out.
 		#[pallet::call_index(2)]
-		#[pallet::weight(T::WeightInfo::end_game())]
+		#[pallet::weight(end_game_weight::<T>())]
 		pub fn end_game(
 			origin: OriginFor<T>,
 			game_index: u32,
 			winner: T::AccountId,
-		) -> DispatchResult {
+		) -> DispatchResultWithPostInfo {
 			let caller = ensure_signed(origin)?;
 
 			// Retrieve game
@@ -231,9 +928,11 @@ pub mod pallet {
 
 			// Check if game has ended
 			ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
+			ensure!(game.house_difficulty.is_none(), Error::<T>::HouseGameIsPlayedOnChain);
 
 			// Retrieve players
-			let payout_addresses = game.payout_addresses;
+			let caller = Self::player_of(caller, game_index, &game);
+			let payout_addresses = game.payout_addresses.clone();
 			let host = payout_addresses.0.ok_or_else(|| Error::<T>::BadAddress)?;
 			let joiner = payout_addresses.1.ok_or_else(|| Error::<T>::BadAddress)?;
 
@@ -243,56 +942,45 @@ pub mod pallet {
 
 			// Update handshake and avoid writing to storage if already set
 			let new_handshake = match Self::update_handshake(
-				game.handshake,
+				game.handshake.clone(),
 				caller.clone(),
 				host.clone(),
 				winner.clone(),
 			) {
 				Ok(handshake) => handshake,
-				Err(err) => return Err(err),
+				Err(err) => return Err(err.into()),
 			};
 
 			// Check if both players have agreed on the winner
-			let mut winner_agreed = true;
-			if new_handshake.0 == None || new_handshake.1 == None {
+			let actual_weight = if new_handshake.0 == None || new_handshake.1 == None {
 				// A winner has been proposed, pending for the other player to propose.
-				Self::deposit_event(Event::WinnerProposed {
+				Self::deposit_game_event(
 					game_index,
-					winner: winner.clone(),
-					proposer: caller.clone(),
-				});
-				winner_agreed = false;
+					Event::WinnerProposed {
+						game_index,
+						winner: winner.clone(),
+						proposer: caller.clone(),
+					},
+				);
+				T::WeightInfo::end_game_first_proposal()
 			} else if new_handshake.0 != new_handshake.1 {
 				// Both players have proposed a winner, but they don't match.
-				Self::deposit_event(Event::MediationRequested { game_index, proposer: caller });
-				winner_agreed = false;
-			}
-
-			// Update game and write to storage
-			let new_game = Game {
-				bet: game.bet,
-				payout_addresses: (Some(host.clone()), Some(joiner.clone())),
-				ended: winner_agreed.clone(),
-				handshake: new_handshake,
+				Self::deposit_game_event(
+					game_index,
+					Event::MediationRequested { game_index, winner, proposer: caller },
+				);
+				T::WeightInfo::end_game_mismatch()
+			} else {
+				// Both players have agreed on the winner, the declared weight is charged.
+				Self::decide_round(game_index, game, winner, ResolutionKind::Agreement)?;
+				return Ok(().into())
 			};
-			Games::<T>::insert(game_index, new_game);
-
-			if winner_agreed {
-				// Both players have agreed on the winner, transfer jackpot and safeguard deposit
-				let jackpot = game.bet.saturating_mul(2u32.into());
-				let safeguard_deposit = Self::safeguard_deposit();
-
-				// Transfer funds.
-				Self::transfer_from_pallet(host, safeguard_deposit)?;
-				Self::transfer_from_pallet(joiner, safeguard_deposit)?;
-				Self::transfer_from_pallet(winner.clone(), jackpot)?;
-
-				Self::deposit_event(Event::GameEnded { game_index, winner, jackpot });
 
-				return Ok(())
-			}
-
-			Ok(())
+			// Update game and write to storage.
+			let new_game = Game { handshake: new_handshake, ..game };
+			Games::<T>::insert(game_index, new_game);
+			MoveDeadlines::<T>::remove(game_index);
+			Ok(Some(actual_weight).into())
 		}
 
 		/// Set the safeguard deposit value.
@@ -309,14 +997,45 @@ pub mod pallet {
 			Ok(())
 		}
 
+		/// Set the fee paid by the host for each game created.
+		/// Only root can set this value. Fees are kept in the pallet account.
+		#[pallet::call_index(23)]
+		#[pallet::weight(T::WeightInfo::set_game_fee())]
+		pub fn set_game_fee(origin: OriginFor<T>, fee: BalanceOf<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			GameFee::<T>::put(fee);
+			Self::deposit_event(Event::GameFeeSet { fee });
+			Ok(())
+		}
+
+		/// Set the minimum and maximum, if any, bet of created games.
+		/// Only root can set these values.
+		#[pallet::call_index(24)]
+		#[pallet::weight(T::WeightInfo::set_bet_limits())]
+		pub fn set_bet_limits(
+			origin: OriginFor<T>,
+			min_bet: BalanceOf<T>,
+			max_bet: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(
+				max_bet.map_or(true, |max_bet| min_bet <= max_bet),
+				Error::<T>::InvalidBetLimits
+			);
+			BetLimits::<T>::put((min_bet, max_bet));
+			Self::deposit_event(Event::BetLimitsSet { min_bet, max_bet });
+			Ok(())
+		}
+
 		/// Force end a game by it's index.
 		/// Only root can force end a game.
 		/// The winner and deposit beneficiary will receive the jackpot and safeguard deposit
-		/// respectively. The game will be marked as ended.
+		/// respectively. The game will be marked as ended. Games nobody has joined can't be force
+		/// ended, they are cancelled by the host or expire.
 		/// This function is expected to be called in case of dispute and game logic must be handled
-		/// off-chain.
+		/// off-chain. On a series, it settles the whole series regardless of the rounds played.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::force_end_game())]
+		#[pallet::weight(force_end_game_weight::<T>())]
 		pub fn force_end_game(
 			origin: OriginFor<T>,
 			game_index: u32,
@@ -324,25 +1043,34 @@ pub mod pallet {
 			deposit_benefiicary: T::AccountId,
 		) -> DispatchResult {
 			ensure_root(origin)?;
+			Self::do_force_end_game(game_index, winner, deposit_benefiicary)
+		}
 
-			Games::<T>::try_mutate(game_index, |game| -> DispatchResult {
-				let mut game = game.as_mut().ok_or_else(|| Error::<T>::GameDoesNotExist)?;
-				ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
-
-				// Update and end game. Set handshake to signal decision.
-				game.ended = true;
-				game.handshake = (Some(winner.clone()), Some(winner.clone()));
-
-				// Transfer jackpot and safeguard deposit, bad actor account will not receive the
-				// safeguard deposit.
-				let jackpot = game.bet.saturating_mul(2u32.into());
-				let safeguard_deposit = Self::safeguard_deposit();
-				Self::transfer_from_pallet(deposit_benefiicary.clone(), safeguard_deposit)?;
-				Self::transfer_from_pallet(winner.clone(), jackpot)?;
-
-				Self::deposit_event(Event::GameEnded { game_index, winner, jackpot });
-				Ok(())
-			})?;
+		/// Force end a batch of games.
+		/// Only root can force end games.
+		/// Each entry is settled as `force_end_game` would, with the game index, winner and deposit
+		/// beneficiary. A failing entry is reverted and reported without failing the whole batch.
+		#[pallet::call_index(19)]
+		#[pallet::weight(force_end_games_weight::<T>(settlements.len() as u32))]
+		pub fn force_end_games(
+			origin: OriginFor<T>,
+			settlements: BoundedVec<(u32, T::AccountId, T::AccountId), T::MaxForceEndBatch>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			for (game_index, winner, deposit_beneficiary) in settlements {
+				match with_storage_layer(|| {
+					Self::do_force_end_game(game_index, winner, deposit_beneficiary)
+				}) {
+					Ok(()) => Self::deposit_game_event(
+						game_index,
+						Event::ForceEndSucceeded { game_index },
+					),
+					Err(error) => Self::deposit_game_event(
+						game_index,
+						Event::ForceEndFailed { game_index, error },
+					),
+				}
+			}
 			Ok(())
 		}
 
@@ -361,6 +1089,645 @@ pub mod pallet {
 			Self::deposit_event(Event::FundsWithdrawn { amount, beneficiary });
 			Ok(())
 		}
+
+		/// Create a single-elimination tournament.
+		/// The caller will be the organizer of the tournament.
+		/// The bracket size must be a power of two, the tournament starts once it is full.
+		/// Prize distribution sets the share of the prize pool for each final standing, champion
+		/// first, after the organizer cut has been taken.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::create_tournament())]
+		pub fn create_tournament(
+			origin: OriginFor<T>,
+			entry_fee: BalanceOf<T>,
+			max_players: u32,
+			prize_distribution: Vec<Permill>,
+			organizer_cut: Permill,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(!entry_fee.is_zero(), Error::<T>::CantBeZero);
+			Self::validate_tournament(max_players, &prize_distribution)?;
+			let prize_distribution = BoundedVec::try_from(prize_distribution)
+				.map_err(|_| Error::<T>::InvalidPrizeDistribution)?;
+
+			// Create new tournament and write to storage
+			let tournament_index = Self::tournament_index();
+			let tournament = Tournament {
+				organizer: caller.clone(),
+				entry_fee,
+				max_players,
+				prize_distribution,
+				organizer_cut,
+				players: Default::default(),
+				round: 0,
+				round_games: Default::default(),
+				advancing: Default::default(),
+				eliminated: Default::default(),
+				status: TournamentStatus::Registering,
+			};
+			let new_tournament_index =
+				tournament_index.checked_add(1).ok_or_else(|| Error::<T>::IndexOverflow)?;
+			Tournaments::<T>::insert(tournament_index, tournament);
+			TournamentIndex::<T>::put(new_tournament_index);
+			Self::deposit_event(Event::TournamentCreated { tournament_index, organizer: caller });
+			Ok(())
+		}
+
+		/// Register in a tournament by it's index.
+		/// The entry fee will be transferred to the pallet account.
+		/// The last player to register fills the bracket and starts the first round.
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::register_for_tournament(T::MaxTournamentPlayers::get()))]
+		pub fn register_for_tournament(
+			origin: OriginFor<T>,
+			tournament_index: u32,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			Tournaments::<T>::try_mutate(tournament_index, |tournament| -> DispatchResult {
+				let tournament =
+					tournament.as_mut().ok_or_else(|| Error::<T>::TournamentDoesNotExist)?;
+				ensure!(
+					tournament.status == TournamentStatus::Registering,
+					Error::<T>::TournamentNotRegistering
+				);
+				ensure!(!tournament.players.contains(&caller), Error::<T>::AlreadyRegistered);
+
+				T::Currency::transfer(
+					&caller,
+					&Self::account_id(),
+					tournament.entry_fee,
+					KeepAlive,
+				)?;
+				tournament
+					.players
+					.try_push(caller.clone())
+					.map_err(|_| Error::<T>::TournamentFull)?;
+				Self::deposit_event(Event::TournamentPlayerRegistered {
+					tournament_index,
+					player: caller,
+				});
+
+				// Bracket is full, reserve a slot for every bracket game and create the first
+				// round.
+				if tournament.players.len() as u32 == tournament.max_players {
+					Self::reserve_open_games(tournament.max_players.saturating_sub(1))?;
+					let players = tournament.players.to_vec();
+					Self::start_round(tournament_index, tournament, players)?;
+				}
+				Ok(())
+			})
+		}
+
+		/// Cancel a tournament by it's index.
+		/// Only the organizer can cancel and only while players are still registering.
+		/// Entry fees are refunded to the registered players.
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::cancel_tournament(T::MaxTournamentPlayers::get()))]
+		pub fn cancel_tournament(origin: OriginFor<T>, tournament_index: u32) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			Tournaments::<T>::try_mutate(tournament_index, |tournament| -> DispatchResult {
+				let tournament =
+					tournament.as_mut().ok_or_else(|| Error::<T>::TournamentDoesNotExist)?;
+				ensure!(tournament.organizer == caller, Error::<T>::NotTheOrganizer);
+				ensure!(
+					tournament.status == TournamentStatus::Registering,
+					Error::<T>::TournamentNotRegistering
+				);
+				Self::refund_entry_fees(tournament)?;
+				tournament.status = TournamentStatus::Cancelled;
+				Ok(())
+			})?;
+
+			Self::deposit_event(Event::TournamentCancelled { tournament_index });
+			Ok(())
+		}
+
+		/// Place a side bet on a game by it's index.
+		/// Any account that is not playing the game can back the host or the joiner once the game
+		/// has been joined and until a winner is proposed.
+		/// The amount will be transferred to the pallet account and added to the game pool.
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::place_side_bet())]
+		pub fn place_side_bet(
+			origin: OriginFor<T>,
+			game_index: u32,
+			on_host: bool,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::CantBeZero);
+
+			// Retrieve game and check it is open for side bets
+			let game = Self::games(game_index).ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+			ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
+			let (host, joiner) = game.payout_addresses;
+			let joiner = joiner.ok_or_else(|| Error::<T>::GameNotJoined)?;
+			ensure!(
+				host.as_ref() != Some(&caller) && joiner != caller,
+				Error::<T>::PlayersCantSideBet
+			);
+			ensure!(game.handshake == (None, None), Error::<T>::SideBetsClosed);
+
+			SideBets::<T>::try_mutate(game_index, |side_bets| -> DispatchResult {
+				side_bets
+					.try_push(SideBet { bettor: caller.clone(), on_host, amount })
+					.map_err(|_| Error::<T>::TooManySideBets)?;
+				T::Currency::transfer(&caller, &Self::account_id(), amount, KeepAlive)?;
+				Ok(())
+			})?;
+
+			Self::deposit_game_event(
+				game_index,
+				Event::SideBetPlaced { game_index, bettor: caller, on_host, amount },
+			);
+			Ok(())
+		}
+
+		/// Play a move on chain.
+		/// Players alternate, the first mover of the round opens the board. Against the house, the
+		/// house replies in the same call.
+		/// As soon as the board is won the round is decided as if both players agreed on the
+		/// winner, a drawn board is cleared to replay the round.
+		/// Once a winner has been proposed the round is settled through the handshake or the
+		/// dispute flow, so no more moves are accepted.
+		#[pallet::call_index(10)]
+		#[pallet::weight(
+			T::WeightInfo::play_move(T::MaxBoardSize::get())
+				.saturating_add(T::WeightInfo::house_move(T::MaxHouseDifficulty::get() as u32))
+				.saturating_add(end_game_weight::<T>())
+		)]
+		pub fn play_move(
+			origin: OriginFor<T>,
+			game_index: u32,
+			game_move: MoveOf<T>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			// Retrieve game and players
+			let game = Self::games(game_index).ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+			ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
+			let host = game.payout_addresses.0.clone().ok_or_else(|| Error::<T>::BadAddress)?;
+			let joiner =
+				game.payout_addresses.1.clone().ok_or_else(|| Error::<T>::GameNotJoined)?;
+			let caller = Self::player_of(caller, game_index, &game);
+			let side = if caller == host {
+				Side::Host
+			} else if caller == joiner {
+				Side::Joiner
+			} else {
+				return Err(Error::<T>::NotAPlayer.into())
+			};
+			ensure!(game.handshake == (None, None), Error::<T>::WinnerProposed);
+
+			// Check turn and apply the move
+			let mut board = Self::boards(game_index)
+				.or_else(|| T::Rules::new_board(game.board_size, game.win_length))
+				.ok_or_else(|| Error::<T>::InvalidBoardSize)?;
+			ensure!(side == Self::side_on_turn(&game, &board), Error::<T>::NotYourTurn);
+			T::Rules::apply_move(&mut board, side, &game_move)
+				.map_err(|_| Error::<T>::InvalidMove)?;
+			Self::deposit_game_event(
+				game_index,
+				Event::MovePlayed { game_index, player: caller, game_move },
+			);
+			let mut outcome: BoardOutcome = T::Rules::outcome(&board).into();
+
+			// The house replies in the same block.
+			if let (Some(difficulty), BoardOutcome::InProgress) = (game.house_difficulty, outcome) {
+				if let Some(house_move) = Self::house_move(&board, difficulty) {
+					T::Rules::apply_move(&mut board, Side::Joiner, &house_move)
+						.map_err(|_| Error::<T>::InvalidMove)?;
+					Self::deposit_game_event(
+						game_index,
+						Event::MovePlayed {
+							game_index,
+							player: joiner.clone(),
+							game_move: house_move,
+						},
+					);
+					outcome = T::Rules::outcome(&board).into();
+				}
+			}
+
+			match outcome {
+				BoardOutcome::InProgress => {
+					Boards::<T>::insert(game_index, board);
+					Self::set_move_deadline(game_index);
+					Ok(())
+				},
+				BoardOutcome::Draw => {
+					Boards::<T>::remove(game_index);
+					Self::set_move_deadline(game_index);
+					Self::deposit_game_event(game_index, Event::RoundDrawn { game_index });
+					Ok(())
+				},
+				BoardOutcome::Won(Side::Host) =>
+					Self::decide_round(game_index, game, host, ResolutionKind::Board),
+				BoardOutcome::Won(Side::Joiner) =>
+					Self::decide_round(game_index, game, joiner, ResolutionKind::Board),
+			}
+		}
+
+		/// Create a new game against the house.
+		/// The caller will be the host of the game and the house joins it right away, staking the
+		/// same bet and safeguard deposit from the house pot. The bet must be within the bet limits
+		/// and the caller pays the game fee.
+		/// The game is a single round on a classic 3x3 board where the house looks `difficulty`
+		/// moves ahead. The caller has `MoveTimeout` blocks to open it, otherwise the house can
+		/// take the round with `claim_timeout`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::create_vs_house())]
+		pub fn create_vs_house(
+			origin: OriginFor<T>,
+			bet: BalanceOf<T>,
+			difficulty: u8,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(!bet.is_zero(), Error::<T>::CantBeZero);
+			ensure!(difficulty <= T::MaxHouseDifficulty::get(), Error::<T>::InvalidDifficulty);
+			Self::ensure_bet_within_limits(bet)?;
+			ensure!(bet <= T::MaxHouseBet::get(), Error::<T>::HouseExposureExceeded);
+			Self::increase_house_exposure(bet)?;
+			Self::open_game()?;
+
+			// Transfer bet amount and safeguard deposit of both sides to pallet account. The
+			// caller pays the storage deposit and the game fee.
+			let house = Self::house_pot_account();
+			let storage_deposit = Self::game_storage_deposit();
+			let transfer_amount = bet.saturating_add(Self::safeguard_deposit());
+			T::Currency::transfer(
+				&caller,
+				&Self::account_id(),
+				transfer_amount.saturating_add(storage_deposit).saturating_add(Self::game_fee()),
+				KeepAlive,
+			)?;
+			T::Currency::transfer(&house, &Self::account_id(), transfer_amount, KeepAlive)?;
+
+			// Create new game and write to storage
+			let game_index = Self::game_index();
+			let game = Game {
+				bet,
+				payout_addresses: (Some(caller.clone()), Some(house.clone())),
+				ended: false,
+				handshake: (None, None),
+				tournament: None,
+				series_length: 1,
+				round_wins: (0, 0),
+				board_size: 3,
+				win_length: 3,
+				house_difficulty: Some(difficulty),
+				storage_deposit,
+				join_bonus: Zero::zero(),
+			};
+			let new_game_index =
+				game_index.checked_add(1).ok_or_else(|| Error::<T>::IndexOverflow)?;
+			Games::<T>::insert(game_index, game);
+			GameIndex::<T>::put(new_game_index);
+			Self::set_move_deadline(game_index);
+			Self::deposit_game_event(
+				game_index,
+				Event::GameCreated {
+					game_index,
+					host: caller,
+					bet,
+					series_length: 1,
+					board_size: 3,
+					win_length: 3,
+					join_bonus: Zero::zero(),
+				},
+			);
+			Self::deposit_game_event(
+				game_index,
+				Event::PlayerJoined { game_index, player: house, bet, join_bonus: Zero::zero() },
+			);
+			Ok(())
+		}
+
+		/// Fund the house pot from the pallet account.
+		/// Only root can fund the house pot.
+		/// Intended to be used with funds left from slashed accounts, anyone can also transfer
+		/// funds to the house pot account directly.
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::fund_house_pot())]
+		pub fn fund_house_pot(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			ensure_root(origin)?;
+			Self::transfer_from_pallet(Self::house_pot_account(), amount)?;
+			Self::deposit_event(Event::HousePotFunded { amount });
+			Ok(())
+		}
+
+		/// Submit the transcript of a disputed round by the game index.
+		/// Only players of a game whose proposed winners don't match can submit, once each.
+		/// The transcript is the list of moves of the round, starting with the first mover. Its
+		/// hash is stored on chain and the transcript is indexed in the offchain database for the
+		/// offchain worker to resolve the dispute.
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::submit_transcript(transcript.len() as u32))]
+		pub fn submit_transcript(
+			origin: OriginFor<T>,
+			game_index: u32,
+			transcript: TranscriptOf<T>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+
+			// Retrieve game and check it is in mediation
+			let game = Self::games(game_index).ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+			ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
+			let host = game.payout_addresses.0.clone().ok_or_else(|| Error::<T>::BadAddress)?;
+			let joiner = game.payout_addresses.1.clone().ok_or_else(|| Error::<T>::BadAddress)?;
+			let caller = Self::player_of(caller, game_index, &game);
+			ensure!(caller == host || caller == joiner, Error::<T>::NotAPlayer);
+			let (host_winner, joiner_winner) = game.handshake;
+			ensure!(
+				host_winner.is_some() && joiner_winner.is_some() && host_winner != joiner_winner,
+				Error::<T>::NotInMediation
+			);
+
+			let hash = T::Hashing::hash_of(&transcript);
+			TranscriptHashes::<T>::try_mutate(game_index, |hashes| -> DispatchResult {
+				let submitted = if caller == host { &mut hashes.0 } else { &mut hashes.1 };
+				ensure!(submitted.is_none(), Error::<T>::TranscriptAlreadySubmitted);
+				*submitted = Some(hash);
+				Ok(())
+			})?;
+			sp_io::offchain_index::set(&transcript_key(game_index, &hash), &transcript.encode());
+
+			Self::deposit_game_event(
+				game_index,
+				Event::TranscriptSubmitted { game_index, player: caller },
+			);
+			Ok(())
+		}
+
+		/// Resolve a dispute by the game index.
+		/// Expected to be called by the offchain worker, but anyone can call it since the
+		/// transcript must match the ones submitted by both players and is replayed on chain.
+		/// The winner of the transcript takes the round as if both players agreed on it.
+		#[pallet::call_index(14)]
+		#[pallet::weight(
+			T::WeightInfo::replay_transcript(transcript.len() as u32)
+				.saturating_add(T::DbWeight::get().reads(2))
+				.saturating_add(end_game_weight::<T>())
+		)]
+		pub fn resolve_dispute(
+			origin: OriginFor<T>,
+			game_index: u32,
+			transcript: TranscriptOf<T>,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			// Retrieve game and check the transcript
+			let game = Self::games(game_index).ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+			ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
+			let hash = Some(T::Hashing::hash_of(&transcript));
+			ensure!(
+				Self::transcript_hashes(game_index) == (hash, hash),
+				Error::<T>::TranscriptMismatch
+			);
+			let winner = Self::replay_transcript(&game, &transcript)?;
+
+			Self::deposit_game_event(
+				game_index,
+				Event::DisputeResolved { game_index, winner: winner.clone() },
+			);
+			Self::decide_round(game_index, game, winner, ResolutionKind::Dispute)
+		}
+
+		/// Claim a timeout by the game index.
+		/// Once the player on turn has missed the move deadline, the opponent takes the round.
+		/// Once a game has waited `OpenGameTimeout` blocks for a joiner, it expires and the bet,
+		/// safeguard deposit and join bonus are refunded to the host.
+		/// Rounds where a winner has been proposed are settled through the handshake instead.
+		/// Submitted as an unsigned transaction, so the claimant doesn't pay fees.
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::claim_timeout().saturating_add(end_game_weight::<T>()))]
+		pub fn claim_timeout(origin: OriginFor<T>, game_index: u32) -> DispatchResult {
+			ensure_none(origin)?;
+
+			// Retrieve game and check the deadline has passed
+			let game = Self::games(game_index).ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+			ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
+			ensure!(game.handshake == (None, None), Error::<T>::WinnerProposed);
+			ensure!(Self::move_deadline_passed(game_index), Error::<T>::NotTimedOut);
+			let host = game.payout_addresses.0.clone().ok_or_else(|| Error::<T>::BadAddress)?;
+			let joiner = match game.payout_addresses.1.clone() {
+				Some(joiner) => joiner,
+				None => return Self::expire_game(game_index, game, host),
+			};
+
+			// The player on turn loses the round
+			let board = Self::boards(game_index)
+				.or_else(|| T::Rules::new_board(game.board_size, game.win_length))
+				.ok_or_else(|| Error::<T>::InvalidBoardSize)?;
+			let winner = match Self::side_on_turn(&game, &board) {
+				Side::Host => joiner,
+				Side::Joiner => host,
+			};
+
+			Self::deposit_game_event(
+				game_index,
+				Event::TimeoutClaimed { game_index, winner: winner.clone() },
+			);
+			Self::decide_round(game_index, game, winner, ResolutionKind::Timeout)
+		}
+
+		/// Register a session key.
+		/// The session key can play moves, propose winners and submit transcripts on behalf of the
+		/// caller, in the game by the passed index or in all of the caller's games if `None`.
+		/// Registering a key again replaces its game.
+		/// Keys are registered per player, so registering a key never affects other players.
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::set_session_key())]
+		pub fn set_session_key(
+			origin: OriginFor<T>,
+			session_key: T::AccountId,
+			game_index: Option<u32>,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			ensure!(session_key != caller, Error::<T>::InvalidSessionKey);
+
+			SessionKeys::<T>::insert(&caller, &session_key, game_index);
+
+			Self::deposit_event(Event::SessionKeySet { player: caller, session_key, game_index });
+			Ok(())
+		}
+
+		/// Remove a session key registered by the caller.
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::remove_session_key())]
+		pub fn remove_session_key(
+			origin: OriginFor<T>,
+			session_key: T::AccountId,
+		) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			SessionKeys::<T>::take(&caller, &session_key)
+				.ok_or_else(|| Error::<T>::NotTheSessionKeyOwner)?;
+
+			Self::deposit_event(Event::SessionKeyRemoved { player: caller, session_key });
+			Ok(())
+		}
+
+		/// Remove an ended game from storage.
+		/// Anyone can prune an ended game, the storage deposit is refunded to the host.
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::prune_game())]
+		pub fn prune_game(origin: OriginFor<T>, game_index: u32) -> DispatchResult {
+			ensure_signed(origin)?;
+			let game = Self::games(game_index).ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+			ensure!(game.ended, Error::<T>::GameNotEnded);
+			let host = game.payout_addresses.0.ok_or_else(|| Error::<T>::BadAddress)?;
+
+			Games::<T>::remove(game_index);
+			RematchOffers::<T>::remove(game_index);
+			Self::transfer_from_pallet(host, game.storage_deposit)?;
+
+			Self::deposit_game_event(
+				game_index,
+				Event::GamePruned { game_index, storage_deposit: game.storage_deposit },
+			);
+			Ok(())
+		}
+
+		/// Offer a rematch of an ended game to the other player.
+		/// The offer can be accepted with `accept_rematch` for `RematchOfferTimeout` blocks.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::offer_rematch())]
+		pub fn offer_rematch(origin: OriginFor<T>, game_index: u32) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let game = Self::games(game_index).ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+			ensure!(game.ended, Error::<T>::GameNotEnded);
+			ensure!(
+				game.tournament.is_none() && game.house_difficulty.is_none(),
+				Error::<T>::RematchNotAllowed
+			);
+			let (host, joiner) = game.payout_addresses;
+			ensure!(
+				Some(&caller) == host.as_ref() || Some(&caller) == joiner.as_ref(),
+				Error::<T>::NotAPlayer
+			);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			if let Some((_, expires_at)) = Self::rematch_offers(game_index) {
+				ensure!(now > expires_at, Error::<T>::RematchAlreadyOffered);
+			}
+			let expires_at = now.saturating_add(T::RematchOfferTimeout::get());
+			RematchOffers::<T>::insert(game_index, (caller.clone(), expires_at));
+
+			Self::deposit_game_event(
+				game_index,
+				Event::RematchOffered { game_index, player: caller, expires_at },
+			);
+			Ok(())
+		}
+
+		/// Accept the rematch offered by the other player of an ended game.
+		/// A new game is created with the same players, bet and settings. The joiner of the ended
+		/// game hosts the rematch, so the first move is swapped. Bets and safeguard deposits of
+		/// both players are transferred to the pallet account, the new host pays the storage
+		/// deposit and the game fee. The bet must still be within the bet limits.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::accept_rematch())]
+		pub fn accept_rematch(origin: OriginFor<T>, game_index: u32) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let game = Self::games(game_index).ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+			let (offerer, expires_at) =
+				Self::rematch_offers(game_index).ok_or_else(|| Error::<T>::NoRematchOffer)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now <= expires_at, Error::<T>::RematchOfferExpired);
+			ensure!(caller != offerer, Error::<T>::OwnRematchOffer);
+			let old_host = game.payout_addresses.0.ok_or_else(|| Error::<T>::BadAddress)?;
+			let old_joiner = game.payout_addresses.1.ok_or_else(|| Error::<T>::BadAddress)?;
+			ensure!(caller == old_host || caller == old_joiner, Error::<T>::NotAPlayer);
+			RematchOffers::<T>::remove(game_index);
+			Self::ensure_bet_within_limits(game.bet)?;
+			Self::open_game()?;
+
+			// Escrow the stakes of both players, the new host pays the storage deposit and the
+			// game fee.
+			let (host, joiner) = (old_joiner, old_host);
+			let storage_deposit = Self::game_storage_deposit();
+			let transfer_amount = game.bet.saturating_add(Self::safeguard_deposit());
+			T::Currency::transfer(
+				&host,
+				&Self::account_id(),
+				transfer_amount.saturating_add(storage_deposit).saturating_add(Self::game_fee()),
+				KeepAlive,
+			)?;
+			T::Currency::transfer(&joiner, &Self::account_id(), transfer_amount, KeepAlive)?;
+
+			// Create new game and write to storage
+			let rematch_index = Self::game_index();
+			let rematch = Game {
+				payout_addresses: (Some(host.clone()), Some(joiner.clone())),
+				ended: false,
+				handshake: (None, None),
+				round_wins: (0, 0),
+				storage_deposit,
+				join_bonus: Zero::zero(),
+				..game
+			};
+			let new_game_index =
+				rematch_index.checked_add(1).ok_or_else(|| Error::<T>::IndexOverflow)?;
+			Games::<T>::insert(rematch_index, rematch);
+			GameIndex::<T>::put(new_game_index);
+
+			Self::deposit_game_event(
+				game_index,
+				Event::RematchAccepted { game_index, rematch_index },
+			);
+			Self::deposit_game_event(
+				rematch_index,
+				Event::GameCreated {
+					game_index: rematch_index,
+					host,
+					bet: game.bet,
+					series_length: game.series_length,
+					board_size: game.board_size,
+					win_length: game.win_length,
+					join_bonus: Zero::zero(),
+				},
+			);
+			Self::deposit_game_event(
+				rematch_index,
+				Event::PlayerJoined {
+					game_index: rematch_index,
+					player: joiner,
+					bet: game.bet,
+					join_bonus: Zero::zero(),
+				},
+			);
+			Ok(())
+		}
+
+		/// Cancel a game nobody has joined.
+		/// Only the host can cancel its game. The bet, safeguard deposit and join bonus are
+		/// refunded and the game is ended, the storage deposit is refunded when it is pruned.
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::cancel_game())]
+		pub fn cancel_game(origin: OriginFor<T>, game_index: u32) -> DispatchResult {
+			let caller = ensure_signed(origin)?;
+			let refund = Games::<T>::try_mutate(game_index, |game| -> Result<_, DispatchError> {
+				let mut game = game.as_mut().ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+				ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
+				ensure!(game.payout_addresses.0.as_ref() == Some(&caller), Error::<T>::NotAPlayer);
+				ensure!(game.payout_addresses.1.is_none(), Error::<T>::GameFull);
+
+				game.ended = true;
+				Self::close_game();
+				MoveDeadlines::<T>::remove(game_index);
+				let refund = game
+					.bet
+					.saturating_add(Self::safeguard_deposit())
+					.saturating_add(game.join_bonus);
+				Self::transfer_from_pallet(caller, refund)?;
+				Ok(refund)
+			})?;
+
+			Self::deposit_game_event(game_index, Event::GameCancelled { game_index, refund });
+			Ok(())
+		}
 	}
 }
 
@@ -371,12 +1738,248 @@ impl<T: Config> Pallet<T> {
 		T::PalletId::get().into_account_truncating()
 	}
 
+	/// Returns the topic the events of a game are indexed by.
+	/// Light clients can follow a single game by subscribing to this topic.
+	pub fn game_topic(game_index: u32) -> T::Hash {
+		T::Hashing::hash_of(&(b"tictactoe::game", game_index))
+	}
+
+	/// Deposit an event of a game, indexed by the topic of the game.
+	pub(crate) fn deposit_game_event(game_index: u32, event: Event<T>) {
+		let event = <T as Config>::RuntimeEvent::from(event);
+		frame_system::Pallet::<T>::deposit_event_indexed(
+			&[Self::game_topic(game_index)],
+			event.into(),
+		);
+	}
+
+	/// Returns the storage deposit charged for a new game.
+	pub fn game_storage_deposit() -> BalanceOf<T> {
+		let bytes = Game::<BalanceOf<T>, T::AccountId>::max_encoded_len() as u32;
+		T::GameDepositPerByte::get().saturating_mul(bytes.into())
+	}
+
+	/// Check that the bet of a new game is within the bet limits.
+	/// Bracket games hold no bet, so the limits only apply to games created with a bet.
+	fn ensure_bet_within_limits(bet: BalanceOf<T>) -> DispatchResult {
+		let (min_bet, max_bet) = Self::bet_limits();
+		ensure!(
+			bet >= min_bet && max_bet.map_or(true, |max_bet| bet <= max_bet),
+			Error::<T>::BetOutOfLimits
+		);
+		Ok(())
+	}
+
+	/// Count a new open game, failing if `MaxOpenGames` games haven't ended yet.
+	fn open_game() -> DispatchResult {
+		Self::reserve_open_games(1)
+	}
+
+	/// Count `count` games as open at once, failing if it would exceed `MaxOpenGames`.
+	/// Each reserved slot is released by `close_game` when one of the games ends.
+	pub(crate) fn reserve_open_games(count: u32) -> DispatchResult {
+		OpenGames::<T>::try_mutate(|open_games| {
+			let reserved = open_games.saturating_add(count);
+			ensure!(reserved <= T::MaxOpenGames::get(), Error::<T>::TooManyOpenGames);
+			*open_games = reserved;
+			Ok(())
+		})
+	}
+
+	/// Stop counting an ended game as open.
+	fn close_game() {
+		OpenGames::<T>::mutate(|open_games| *open_games = open_games.saturating_sub(1));
+	}
+
 	/// Send funds from the pallet account to a beneficiary.
 	fn transfer_from_pallet(beneficiary: T::AccountId, amount: BalanceOf<T>) -> DispatchResult {
 		T::Currency::transfer(&Self::account_id(), &beneficiary, amount, KeepAlive)?;
 		Ok(())
 	}
 
+	/// Returns the side that must play the next move on a board.
+	/// The first mover of the round opens the board and players alternate afterwards.
+	fn side_on_turn(game: &Game<BalanceOf<T>, T::AccountId>, board: &BoardOf<T>) -> Side {
+		let first_mover =
+			if game.first_mover() == game.payout_addresses.0 { Side::Host } else { Side::Joiner };
+		if T::Rules::moves_played(board) % 2 == 0 {
+			first_mover
+		} else {
+			first_mover.opponent()
+		}
+	}
+
+	/// Count a round won by `winner`, either agreed by both players or decided on chain.
+	/// The handshake and board are reset for the next round until a player wins the majority of
+	/// the rounds. Then the game ends, side bets are settled and the jackpot is transferred or,
+	/// on a bracket game, the winner advances in the tournament.
+	fn decide_round(
+		game_index: u32,
+		game: Game<BalanceOf<T>, T::AccountId>,
+		winner: T::AccountId,
+		resolution: ResolutionKind,
+	) -> DispatchResult {
+		let rounds_to_win = game.rounds_to_win();
+		let host = game.payout_addresses.0.clone().ok_or_else(|| Error::<T>::BadAddress)?;
+		let joiner = game.payout_addresses.1.clone().ok_or_else(|| Error::<T>::BadAddress)?;
+
+		// Count the round and check if the series has been decided
+		let mut round_wins = game.round_wins;
+		if winner == host {
+			round_wins.0 = round_wins.0.saturating_add(1);
+		} else {
+			round_wins.1 = round_wins.1.saturating_add(1);
+		}
+		let series_won = round_wins.0.max(round_wins.1) >= rounds_to_win;
+
+		// Update game and write to storage. Handshake is reset if the series goes on.
+		let new_game = Game {
+			ended: series_won,
+			handshake: if series_won {
+				(Some(winner.clone()), Some(winner.clone()))
+			} else {
+				(None, None)
+			},
+			round_wins,
+			..game.clone()
+		};
+		Games::<T>::insert(game_index, new_game);
+		Boards::<T>::remove(game_index);
+		Self::clear_transcripts(game_index);
+		MoveDeadlines::<T>::remove(game_index);
+
+		if !series_won {
+			Self::deposit_game_event(
+				game_index,
+				Event::RoundWon { game_index, winner, round_wins, resolution },
+			);
+			return Ok(())
+		}
+		FeelessCalls::<T>::remove(game_index);
+		Self::close_game();
+
+		// Share the side bet pool among the backers of the winner.
+		Self::settle_side_bets(game_index, winner == host)?;
+
+		if let Some(tournament_index) = game.tournament {
+			// Bracket games hold no stake, the winner advances in the tournament.
+			let loser = if winner == host { joiner } else { host };
+			Self::deposit_game_event(
+				game_index,
+				Event::GameEnded {
+					game_index,
+					winner: winner.clone(),
+					jackpot: Zero::zero(),
+					resolution,
+				},
+			);
+			return Self::advance_tournament(tournament_index, game_index, winner, loser)
+		}
+
+		// Transfer jackpot and safeguard deposit
+		let jackpot = game.bet.saturating_mul(2u32.into());
+		let safeguard_deposit = Self::safeguard_deposit();
+
+		// Transfer funds.
+		Self::transfer_from_pallet(host.clone(), safeguard_deposit)?;
+		Self::transfer_from_pallet(joiner.clone(), safeguard_deposit)?;
+		Self::transfer_from_pallet(winner.clone(), jackpot)?;
+
+		for player in [host, joiner] {
+			Self::deposit_game_event(
+				game_index,
+				Event::DepositReturned { game_index, player, amount: safeguard_deposit },
+			);
+		}
+		Self::deposit_game_event(
+			game_index,
+			Event::GameEnded { game_index, winner, jackpot, resolution },
+		);
+		Ok(())
+	}
+
+	/// End a game in favor of `winner`, returning the safeguard deposit to `deposit_beneficiary`.
+	/// On a series, it settles the whole series regardless of the rounds played.
+	fn do_force_end_game(
+		game_index: u32,
+		winner: T::AccountId,
+		deposit_beneficiary: T::AccountId,
+	) -> DispatchResult {
+		Games::<T>::try_mutate(game_index, |game| -> DispatchResult {
+			let mut game = game.as_mut().ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+			ensure!(!game.ended, Error::<T>::GameAlreadyEnded);
+			ensure!(game.payout_addresses.1.is_some(), Error::<T>::GameNotJoined);
+
+			// Update and end game. Set handshake to signal decision.
+			game.ended = true;
+			Self::close_game();
+			game.handshake = (Some(winner.clone()), Some(winner.clone()));
+			Boards::<T>::remove(game_index);
+			Self::clear_transcripts(game_index);
+			MoveDeadlines::<T>::remove(game_index);
+			FeelessCalls::<T>::remove(game_index);
+
+			// Share the side bet pool among the backers of the winner.
+			let host_won = game.payout_addresses.0.as_ref() == Some(&winner);
+			Self::settle_side_bets(game_index, host_won)?;
+
+			if let Some(tournament_index) = game.tournament {
+				// Bracket games hold no stake, the winner advances in the tournament.
+				let host = game.payout_addresses.0.clone().ok_or_else(|| Error::<T>::BadAddress)?;
+				let joiner =
+					game.payout_addresses.1.clone().ok_or_else(|| Error::<T>::BadAddress)?;
+				ensure!(winner == host || winner == joiner, Error::<T>::NotAPlayer);
+				let loser = if winner == host { joiner } else { host };
+				Self::deposit_game_event(
+					game_index,
+					Event::GameEnded {
+						game_index,
+						winner: winner.clone(),
+						jackpot: Zero::zero(),
+						resolution: ResolutionKind::Forced,
+					},
+				);
+				return Self::advance_tournament(tournament_index, game_index, winner, loser)
+			}
+
+			// Transfer jackpot and safeguard deposit, bad actor account will not receive the
+			// safeguard deposit.
+			let jackpot = game.bet.saturating_mul(2u32.into());
+			let safeguard_deposit = Self::safeguard_deposit();
+			Self::transfer_from_pallet(deposit_beneficiary.clone(), safeguard_deposit)?;
+			Self::transfer_from_pallet(winner.clone(), jackpot)?;
+
+			for player in [&game.payout_addresses.0, &game.payout_addresses.1].into_iter().flatten()
+			{
+				let event = if *player == deposit_beneficiary {
+					Event::DepositReturned {
+						game_index,
+						player: player.clone(),
+						amount: safeguard_deposit,
+					}
+				} else {
+					Event::DepositSlashed {
+						game_index,
+						player: player.clone(),
+						amount: safeguard_deposit,
+					}
+				};
+				Self::deposit_game_event(game_index, event);
+			}
+			Self::deposit_game_event(
+				game_index,
+				Event::GameEnded {
+					game_index,
+					winner,
+					jackpot,
+					resolution: ResolutionKind::Forced,
+				},
+			);
+			Ok(())
+		})?;
+		Ok(())
+	}
+
 	/// Update handshake and avoid writing to storage if already set
 	/// Host proposed winner is store in handshake.0
 	/// Joiner proposed winner is store in handshake.1
@@ -403,3 +2006,31 @@ impl<T: Config> Pallet<T> {
 		Ok(new_handshake)
 	}
 }
+
+/// Weight of ending a game, the maximum across proposing a winner, requesting mediation and
+/// deciding the game. Deciding is bounded by advancing the largest tournament and settling a full
+/// side bet pool.
+fn end_game_weight<T: Config>() -> Weight {
+	let decision = T::WeightInfo::end_game_agreement()
+		.max(T::WeightInfo::settle_tournament_game(T::MaxTournamentPlayers::get()))
+		.saturating_add(T::WeightInfo::settle_side_bets(T::MaxSideBetsPerGame::get()));
+	T::WeightInfo::end_game_first_proposal()
+		.max(T::WeightInfo::end_game_mismatch())
+		.max(decision)
+}
+
+/// Weight of force ending a game, the maximum across settling a game with a full side bet pool and
+/// advancing the largest tournament, whose bracket games can hold side bets too.
+fn force_end_game_weight<T: Config>() -> Weight {
+	let max_side_bets = T::MaxSideBetsPerGame::get();
+	let tournament = T::WeightInfo::force_end_tournament_game(T::MaxTournamentPlayers::get())
+		.saturating_add(T::WeightInfo::settle_side_bets(max_side_bets));
+	T::WeightInfo::force_end_game(max_side_bets).max(tournament)
+}
+
+/// Weight of force ending a batch of `n` games. `force_end_games` is measured on plain disputed
+/// games, each entry is bounded by what `force_end_game` adds on top of them.
+fn force_end_games_weight<T: Config>(n: u32) -> Weight {
+	let extra = force_end_game_weight::<T>().saturating_sub(T::WeightInfo::force_end_game(0));
+	T::WeightInfo::force_end_games(n).saturating_add(extra.saturating_mul(n as u64))
+}
@@ -1,19 +1,28 @@
 use super::*;
 use crate as pallet_tictactoe;
 
+use codec::{Decode, Encode};
 use frame_support::{
-	parameter_types,
-	traits::{ConstU32, ConstU64},
+	ensure, parameter_types,
+	traits::{ConstU32, ConstU64, ConstU8, Currency, GenesisBuild},
 };
+use scale_info::TypeInfo;
 
+use frame_system::offchain::AppCrypto;
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
-	traits::{BlakeTwo256, IdentityLookup},
+	testing::{Header, TestSignature, TestXt, UintAuthorityId},
+	traits::{
+		BlakeTwo256, DispatchInfoOf, Extrinsic as ExtrinsicT, IdentityLookup, SignedExtension,
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
 };
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -73,6 +82,8 @@ impl pallet_balances::Config for Test {
 
 parameter_types! {
 	pub const TictactoePalletId: PalletId = PalletId(*b"py/tctct");
+	pub static GameDepositPerByte: u64 = 0;
+	pub static MaxOpenGames: u32 = 16;
 }
 
 impl pallet_tictactoe::Config for Test {
@@ -80,15 +91,115 @@ impl pallet_tictactoe::Config for Test {
 	type PalletId = TictactoePalletId;
 	type Currency = Balances;
 	type WeightInfo = ();
+	type MaxTournamentPlayers = ConstU32<8>;
+	type MaxPrizePositions = ConstU32<4>;
+	type MaxSeriesLength = ConstU32<5>;
+	type MaxSideBetsPerGame = ConstU32<3>;
+	type MaxBoardSize = ConstU32<5>;
+	type Rules = pallet_tictactoe::KInARow<ConstU32<5>>;
+	type MaxHouseBet = ConstU64<20>;
+	type MaxHouseExposurePerBlock = ConstU64<30>;
+	type MaxHouseDifficulty = ConstU8<4>;
+	type AuthorityId = TestAuthId;
+	type MoveTimeout = ConstU64<10>;
+	type OpenGameTimeout = ConstU64<20>;
+	type UnsignedPriority = ConstU64<100>;
+	type GameDepositPerByte = GameDepositPerByte;
+	type MaxOpenGames = MaxOpenGames;
+	type MaxForceEndBatch = ConstU32<4>;
+	type RematchOfferTimeout = ConstU64<5>;
+}
+
+/// Offchain worker keys of the mock runtime, accounts are the `u64` of the key.
+pub struct TestAuthId;
+
+impl AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: UintAuthorityId,
+		_account: u64,
+		nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+/// Signed extension charging a flat fee of 1, stands for `ChargeTransactionPayment`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug, TypeInfo)]
+pub struct TestFee;
+
+impl SignedExtension for TestFee {
+	const IDENTIFIER: &'static str = "TestFee";
+	type AccountId = u64;
+	type Call = RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &u64,
+		_call: &RuntimeCall,
+		_info: &DispatchInfoOf<RuntimeCall>,
+		_len: usize,
+	) -> TransactionValidity {
+		ensure!(Balances::free_balance(who) > 1, InvalidTransaction::Payment);
+		Ok(ValidTransaction { priority: 1, ..Default::default() })
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &u64,
+		call: &RuntimeCall,
+		info: &DispatchInfoOf<RuntimeCall>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		self.validate(who, call, info, len)?;
+		let _ = Balances::slash(who, 1);
+		Ok(())
+	}
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
+	new_test_ext_with_genesis(Default::default())
+}
+
+// Build genesis storage with the passed pallet genesis config.
+pub fn new_test_ext_with_genesis(
+	tictactoe: pallet_tictactoe::GenesisConfig<Test>,
+) -> sp_io::TestExternalities {
 	let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 	pallet_balances::GenesisConfig::<Test> {
 		balances: vec![(1, 100), (2, 100), (3, 100), (4, 100), (5, 100)],
 	}
 	.assimilate_storage(&mut t)
 	.unwrap();
+	tictactoe.assimilate_storage(&mut t).unwrap();
 	t.into()
 }
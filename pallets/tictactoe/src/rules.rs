@@ -0,0 +1,262 @@
+//! Game rules.
+//!
+//! Bets, handshakes and mediation don't depend on the game being played. The rules are provided
+//! by the runtime through [`GameRules`], so boards can be tracked on chain and the winner of a
+//! round detected without waiting for the handshake. [`TicTacToe`] is the classic 3x3 game and
+//! [`KInARow`] generalizes it to NxN boards where a player needs K marks in a row, such as gomoku,
+//! with the board size and win length chosen for each game.
+
+use frame_support::{
+	pallet_prelude::*,
+	sp_std::{marker::PhantomData, prelude::*},
+	CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+
+/// Side of the board a player plays.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum Side {
+	/// The player that created the game.
+	Host,
+	/// The player that joined the game.
+	Joiner,
+}
+
+impl Side {
+	/// Returns the other side of the board.
+	pub fn opponent(self) -> Self {
+		match self {
+			Side::Host => Side::Joiner,
+			Side::Joiner => Side::Host,
+		}
+	}
+}
+
+/// Outcome of a board as seen by the pallet.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum BoardOutcome {
+	/// The board is still being played.
+	InProgress,
+	/// A side has won the round.
+	Won(Side),
+	/// No side can win anymore, the round is replayed.
+	Draw,
+}
+
+/// Reasons for a move to be rejected.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum MoveError {
+	/// The move doesn't point to a cell of the board.
+	OutOfBounds,
+	/// The cell has already been marked.
+	CellTaken,
+	/// The board has already been decided.
+	BoardDecided,
+}
+
+/// Rules of a two player board game.
+pub trait GameRules {
+	/// Board stored on chain while a round is being played.
+	type Board: Parameter + MaxEncodedLen;
+	/// Move submitted by a player.
+	type Move: Parameter + MaxEncodedLen;
+	/// Result of evaluating a board. Games can report richer outcomes, the pallet only needs to
+	/// know if a side won.
+	type Outcome: Into<BoardOutcome>;
+
+	/// Returns an empty board to start a round, or `None` if the rules don't support a board of
+	/// `size` cells per side where `win_length` marks in a row win.
+	fn new_board(size: u8, win_length: u8) -> Option<Self::Board>;
+
+	/// Returns the number of moves played on a board, used to know whose turn it is.
+	fn moves_played(board: &Self::Board) -> u32;
+
+	/// Returns the moves that can be played on a board.
+	fn legal_moves(board: &Self::Board) -> Vec<Self::Move>;
+
+	/// Mark the board with a move of `side`.
+	fn apply_move(
+		board: &mut Self::Board,
+		side: Side,
+		game_move: &Self::Move,
+	) -> Result<(), MoveError>;
+
+	/// Evaluate a board.
+	fn outcome(board: &Self::Board) -> Self::Outcome;
+}
+
+/// Classic 3x3 tic-tac-toe. Moves are cell indexes in row-major order.
+pub struct TicTacToe;
+
+impl GameRules for TicTacToe {
+	type Board = [Option<Side>; 9];
+	type Move = u8;
+	type Outcome = BoardOutcome;
+
+	fn new_board(size: u8, win_length: u8) -> Option<Self::Board> {
+		(size == 3 && win_length == 3).then(|| [None; 9])
+	}
+
+	fn moves_played(board: &Self::Board) -> u32 {
+		count_marks(board)
+	}
+
+	fn legal_moves(board: &Self::Board) -> Vec<Self::Move> {
+		if Self::outcome(board) != BoardOutcome::InProgress {
+			return Vec::new()
+		}
+		empty_cells(board).map(|cell| cell as u8).collect()
+	}
+
+	fn apply_move(
+		board: &mut Self::Board,
+		side: Side,
+		game_move: &Self::Move,
+	) -> Result<(), MoveError> {
+		ensure!(Self::outcome(board) == BoardOutcome::InProgress, MoveError::BoardDecided);
+		mark_cell(board, *game_move as usize, side)
+	}
+
+	fn outcome(board: &Self::Board) -> Self::Outcome {
+		board_outcome(board, 3, 3)
+	}
+}
+
+/// Bound of the cells of a square board with up to `Size` cells per side.
+pub struct Cells<Size>(PhantomData<Size>);
+
+impl<Size: Get<u32>> Get<u32> for Cells<Size> {
+	fn get() -> u32 {
+		Size::get().saturating_mul(Size::get())
+	}
+}
+
+/// Board of a [`KInARow`] game.
+#[derive(
+	CloneNoBound,
+	Encode,
+	Decode,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(MaxSize))]
+#[codec(mel_bound())]
+pub struct KInARowBoard<MaxSize: Get<u32>> {
+	// Cells per side of the board.
+	size: u8,
+	// Marks in a row needed to win.
+	win_length: u8,
+	// Cells in row-major order.
+	cells: BoundedVec<Option<Side>, Cells<MaxSize>>,
+}
+
+/// NxN board where the first player to get K marks in a row, column or diagonal wins.
+/// Each game sets its own board size, up to `MaxSize` cells per side, and win length.
+/// Moves are `(row, column)` pairs.
+pub struct KInARow<MaxSize>(PhantomData<MaxSize>);
+
+impl<MaxSize: Get<u32> + 'static> GameRules for KInARow<MaxSize> {
+	type Board = KInARowBoard<MaxSize>;
+	type Move = (u8, u8);
+	type Outcome = BoardOutcome;
+
+	fn new_board(size: u8, win_length: u8) -> Option<Self::Board> {
+		if win_length < 3 || win_length > size || size as u32 > MaxSize::get() {
+			return None
+		}
+		let cells = (0..size as u32 * size as u32).map(|_| None).collect::<Vec<_>>();
+		Some(KInARowBoard { size, win_length, cells: BoundedVec::try_from(cells).ok()? })
+	}
+
+	fn moves_played(board: &Self::Board) -> u32 {
+		count_marks(&board.cells)
+	}
+
+	fn legal_moves(board: &Self::Board) -> Vec<Self::Move> {
+		if Self::outcome(board) != BoardOutcome::InProgress {
+			return Vec::new()
+		}
+		let size = board.size as usize;
+		empty_cells(&board.cells)
+			.map(|cell| ((cell / size) as u8, (cell % size) as u8))
+			.collect()
+	}
+
+	fn apply_move(
+		board: &mut Self::Board,
+		side: Side,
+		game_move: &Self::Move,
+	) -> Result<(), MoveError> {
+		ensure!(Self::outcome(board) == BoardOutcome::InProgress, MoveError::BoardDecided);
+		let size = board.size as usize;
+		let (row, column) = (game_move.0 as usize, game_move.1 as usize);
+		ensure!(row < size && column < size, MoveError::OutOfBounds);
+		mark_cell(&mut board.cells, row * size + column, side)
+	}
+
+	fn outcome(board: &Self::Board) -> Self::Outcome {
+		board_outcome(&board.cells, board.size as usize, board.win_length as usize)
+	}
+}
+
+/// Count the marked cells of a board.
+fn count_marks(cells: &[Option<Side>]) -> u32 {
+	cells.iter().filter(|cell| cell.is_some()).count() as u32
+}
+
+/// Returns the indexes of the empty cells of a board.
+fn empty_cells(cells: &[Option<Side>]) -> impl Iterator<Item = usize> + '_ {
+	cells
+		.iter()
+		.enumerate()
+		.filter(|(_, cell)| cell.is_none())
+		.map(|(index, _)| index)
+}
+
+/// Mark an empty cell of a board.
+fn mark_cell(cells: &mut [Option<Side>], index: usize, side: Side) -> Result<(), MoveError> {
+	let cell = cells.get_mut(index).ok_or(MoveError::OutOfBounds)?;
+	ensure!(cell.is_none(), MoveError::CellTaken);
+	*cell = Some(side);
+	Ok(())
+}
+
+/// Evaluate a square board of `size` cells per side where `win_length` marks in a row win.
+/// A full board without a winner is a draw.
+fn board_outcome(cells: &[Option<Side>], size: usize, win_length: usize) -> BoardOutcome {
+	// Rows, columns, diagonals and anti-diagonals.
+	let directions: [(isize, isize); 4] = [(0, 1), (1, 0), (1, 1), (1, -1)];
+	let cell_at = |row: isize, column: isize| -> Option<Side> {
+		if row < 0 || column < 0 || row >= size as isize || column >= size as isize {
+			return None
+		}
+		cells.get(row as usize * size + column as usize).copied().flatten()
+	};
+
+	for row in 0..size as isize {
+		for column in 0..size as isize {
+			let side = match cell_at(row, column) {
+				Some(side) => side,
+				None => continue,
+			};
+			for (row_step, column_step) in directions {
+				let in_a_row = (0..win_length as isize)
+					.take_while(|step| {
+						cell_at(row + row_step * step, column + column_step * step) == Some(side)
+					})
+					.count();
+				if in_a_row >= win_length.max(1) {
+					return BoardOutcome::Won(side)
+				}
+			}
+		}
+	}
+
+	if cells.iter().all(|cell| cell.is_some()) {
+		BoardOutcome::Draw
+	} else {
+		BoardOutcome::InProgress
+	}
+}
@@ -0,0 +1,38 @@
+//! Session keys.
+//!
+//! Players can register a session key, either for a single game or for all their games, so they
+//! don't sign every move with their funded account. A session key can only act as its player in
+//! gameplay calls: `play_move`, `end_game` and `submit_transcript`. Bets, deposits and payouts keep
+//! going through the player account.
+//!
+//! Keys are stored under the player that registered them, so any player can register any account
+//! without locking it for others. A key registered by both players of a game acts for neither of
+//! them in that game.
+
+use super::*;
+
+impl<T: Config> Pallet<T> {
+	/// Returns the player `caller` acts for in a game.
+	/// Players act for themselves, session keys act for the player of the game that registered
+	/// them if the key is global or registered for this game.
+	pub(crate) fn player_of(
+		caller: T::AccountId,
+		game_index: u32,
+		game: &Game<BalanceOf<T>, T::AccountId>,
+	) -> T::AccountId {
+		let (host, joiner) = &game.payout_addresses;
+		if host.as_ref() == Some(&caller) || joiner.as_ref() == Some(&caller) {
+			return caller
+		}
+		let registered_by = |player: &Option<T::AccountId>| {
+			let player = player.as_ref()?;
+			let scope = Self::session_keys(player, &caller)?;
+			scope.map_or(true, |scope| scope == game_index).then(|| player.clone())
+		};
+		match (registered_by(host), registered_by(joiner)) {
+			(Some(player), None) | (None, Some(player)) => player,
+			// Unknown key, or registered by both players.
+			_ => caller,
+		}
+	}
+}
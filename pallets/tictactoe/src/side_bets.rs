@@ -0,0 +1,98 @@
+//! Spectator side bets.
+//!
+//! Accounts that are not playing a game can back the host or the joiner once the game has been
+//! joined. Side bets are held in the pallet account in a parimutuel pool per game: when the game
+//! ends, the whole pool is shared among the backers of the winner in proportion to their stake.
+//! If nobody backed the winner, every side bet is refunded.
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	sp_runtime::{
+		helpers_128bit::multiply_rational, traits::SaturatedConversion, ArithmeticError, Rounding,
+	},
+	sp_std::prelude::*,
+};
+
+/// Side bet struct
+#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct SideBet<Balance, AccountId> {
+	// Account that placed the bet.
+	pub(crate) bettor: AccountId,
+	// Indicates if the bet backs the host, otherwise it backs the joiner.
+	pub(crate) on_host: bool,
+	// Amount held in the pool.
+	pub(crate) amount: Balance,
+}
+
+impl<Balance: Copy, AccountId> SideBet<Balance, AccountId> {
+	/// Returns the amount held in the pool.
+	pub fn amount(&self) -> Balance {
+		self.amount
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Share the side bet pool of an ended game among the backers of the winner.
+	/// Shares are rounded down and the rounding remainder goes to the first backer of the winner,
+	/// so the whole pool is paid out.
+	pub(crate) fn settle_side_bets(game_index: u32, host_won: bool) -> DispatchResult {
+		let side_bets = SideBets::<T>::take(game_index);
+		if side_bets.is_empty() {
+			return Ok(())
+		}
+
+		let mut total_pool: BalanceOf<T> = Zero::zero();
+		let mut winning_pool: BalanceOf<T> = Zero::zero();
+		for side_bet in side_bets.iter() {
+			total_pool = total_pool.saturating_add(side_bet.amount);
+			if side_bet.on_host == host_won {
+				winning_pool = winning_pool.saturating_add(side_bet.amount);
+			}
+		}
+
+		// Nobody backed the winner, give every bet back.
+		if winning_pool.is_zero() {
+			return Self::refund_side_bets(game_index, side_bets.into_inner())
+		}
+
+		let mut payouts = Vec::new();
+		let mut paid: BalanceOf<T> = Zero::zero();
+		for side_bet in side_bets.iter().filter(|side_bet| side_bet.on_host == host_won) {
+			let payout = multiply_rational(
+				side_bet.amount.saturated_into(),
+				total_pool.saturated_into(),
+				winning_pool.saturated_into(),
+				Rounding::Down,
+			)
+			.ok_or(ArithmeticError::Overflow)?
+			.saturated_into::<BalanceOf<T>>();
+			paid = paid.saturating_add(payout);
+			payouts.push((side_bet.bettor.clone(), payout));
+		}
+		if let Some((_, payout)) = payouts.first_mut() {
+			*payout = payout.saturating_add(total_pool.saturating_sub(paid));
+		}
+		for (bettor, payout) in payouts {
+			Self::transfer_from_pallet(bettor, payout)?;
+		}
+
+		Self::deposit_game_event(
+			game_index,
+			Event::SideBetsSettled { game_index, host_won, total_pool },
+		);
+		Ok(())
+	}
+
+	/// Give every side bet back to its bettor.
+	pub(crate) fn refund_side_bets(
+		game_index: u32,
+		side_bets: Vec<SideBet<BalanceOf<T>, T::AccountId>>,
+	) -> DispatchResult {
+		for side_bet in side_bets {
+			Self::transfer_from_pallet(side_bet.bettor, side_bet.amount)?;
+		}
+		Self::deposit_game_event(game_index, Event::SideBetsRefunded { game_index });
+		Ok(())
+	}
+}
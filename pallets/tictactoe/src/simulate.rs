@@ -0,0 +1,92 @@
+//! Dry run of `end_game`.
+//!
+//! Wallets query the outcome of an `end_game` call before submitting it through the runtime API
+//! of the pallet. The call is dispatched against the current state in a storage transaction that
+//! is always rolled back, so the prediction runs the same checks as the extrinsic.
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	sp_std::prelude::*,
+	storage::{with_transaction, TransactionOutcome},
+};
+use frame_system::RawOrigin;
+
+/// Predicted outcome of an `end_game` call.
+#[derive(Clone, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum EndGameOutcome<AccountId, Balance> {
+	/// The proposal is recorded, waiting for the other player to propose a winner.
+	ProposalRecorded,
+	/// The players proposed different winners, the game is sent to mediation.
+	MediationNeeded,
+	/// The agreed winner takes the round and the series goes on.
+	RoundWon { winner: AccountId, round_wins: (u32, u32) },
+	/// The game is decided. Payouts list the amount received by each player and side bettor,
+	/// jackpot and safeguard deposits included.
+	Settled { winner: AccountId, payouts: Vec<(AccountId, Balance)> },
+	/// The call fails with this error.
+	Failed(DispatchError),
+}
+
+impl<T: Config> Pallet<T> {
+	/// Predict the outcome of `end_game` called by `caller` proposing `winner`. The state is left
+	/// untouched.
+	pub fn simulate_end_game(
+		game_index: u32,
+		caller: T::AccountId,
+		winner: T::AccountId,
+	) -> EndGameOutcome<T::AccountId, BalanceOf<T>> {
+		with_transaction(|| {
+			let outcome = Self::dry_run_end_game(game_index, caller, winner);
+			TransactionOutcome::Rollback(Ok::<_, DispatchError>(outcome))
+		})
+		.unwrap_or_else(EndGameOutcome::Failed)
+	}
+
+	/// Dispatch `end_game` and compare the game and the balances before and after the call.
+	fn dry_run_end_game(
+		game_index: u32,
+		caller: T::AccountId,
+		winner: T::AccountId,
+	) -> EndGameOutcome<T::AccountId, BalanceOf<T>> {
+		let mut parties: Vec<T::AccountId> = Self::games(game_index)
+			.map(|game| {
+				let (host, joiner) = game.payout_addresses;
+				host.into_iter().chain(joiner).collect()
+			})
+			.unwrap_or_default();
+		for side_bet in SideBets::<T>::get(game_index) {
+			if !parties.contains(&side_bet.bettor) {
+				parties.push(side_bet.bettor);
+			}
+		}
+		let balances: Vec<BalanceOf<T>> = parties.iter().map(T::Currency::free_balance).collect();
+
+		if let Err(err) =
+			Self::end_game(RawOrigin::Signed(caller).into(), game_index, winner.clone())
+		{
+			return EndGameOutcome::Failed(err.error)
+		}
+
+		let game = match Self::games(game_index) {
+			Some(game) => game,
+			None => return EndGameOutcome::Failed(Error::<T>::GameDoesNotExist.into()),
+		};
+		match game.handshake {
+			_ if game.ended => {
+				let payouts = parties
+					.into_iter()
+					.zip(balances)
+					.filter_map(|(party, before)| {
+						let received = T::Currency::free_balance(&party).saturating_sub(before);
+						(!received.is_zero()).then_some((party, received))
+					})
+					.collect();
+				EndGameOutcome::Settled { winner, payouts }
+			},
+			(None, None) => EndGameOutcome::RoundWon { winner, round_wins: game.round_wins },
+			(Some(_), Some(_)) => EndGameOutcome::MediationNeeded,
+			_ => EndGameOutcome::ProposalRecorded,
+		}
+	}
+}
@@ -1,5 +1,26 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	self as pallet_tictactoe, mock::*, rules::MoveError, transcript_key, BoardOutcome,
+	CheckTictactoeMove, Config, EndGameOutcome, Error, Event, GameRules, GameStatus, KInARow,
+	ResolutionKind, Side, TicTacToe, TournamentStatus, WeightInfo,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::GetDispatchInfo,
+	traits::{ConstU32, Hooks},
+	BoundedVec,
+};
+use sp_core::offchain::{
+	testing::{TestOffchainExt, TestTransactionPoolExt},
+	OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
+use sp_runtime::{
+	offchain::storage::StorageValueRef,
+	testing::UintAuthorityId,
+	traits::{BadOrigin, Hash, SignedExtension, ValidateUnsigned},
+	transaction_validity::{InvalidTransaction, TransactionSource},
+	Permill,
+};
 
 #[test]
 fn initial_state() {
@@ -35,6 +56,130 @@ fn set_safeguarde_fails_without_root() {
 	});
 }
 
+#[test]
+fn genesis_config_works() {
+	let genesis = pallet_tictactoe::GenesisConfig::<Test> {
+		safeguard_deposit: 1,
+		game_fee: 2,
+		bet_limits: (5, Some(50)),
+		demo_games: vec![(1, Some(2), 10), (3, None, 20), (4, Some(5), 10)],
+		disputed_demo_games: vec![2],
+	};
+	new_test_ext_with_genesis(genesis).execute_with(|| {
+		assert_eq!(Tictactoe::safeguard_deposit(), 1);
+		assert_eq!(Tictactoe::game_fee(), 2);
+		assert_eq!(Tictactoe::bet_limits(), (5, Some(50)));
+
+		// Demo games are created as regular games.
+		assert_eq!(Tictactoe::game_index(), 3);
+		assert_eq!(Tictactoe::open_games(), 3);
+		assert_eq!(Tictactoe::games(0).unwrap().payout_addresses, (Some(1), Some(2)));
+		assert_eq!(Tictactoe::games(1).unwrap().payout_addresses, (Some(3), None));
+		assert_eq!(Tictactoe::games(0).unwrap().status(), GameStatus::Open);
+		assert_eq!(Tictactoe::games(2).unwrap().status(), GameStatus::InMediation);
+		assert_eq!(Balances::free_balance(&1), 100 - 10 - 1 - 2);
+		assert_eq!(Balances::free_balance(&2), 100 - 10 - 1);
+		assert_eq!(Balances::free_balance(&3), 100 - 20 - 1 - 2);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 4 * 11 + 21 + 2 * 3);
+	});
+}
+
+#[test]
+fn game_fee_and_bet_limits_work() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert!(Tictactoe::set_game_fee(RuntimeOrigin::signed(1), 2).is_err());
+		assert_ok!(Tictactoe::set_game_fee(RuntimeOrigin::root(), 2));
+		System::assert_last_event((Event::GameFeeSet { fee: 2 }).into());
+
+		assert!(Tictactoe::set_bet_limits(RuntimeOrigin::signed(1), 5, Some(20)).is_err());
+		assert_noop!(
+			Tictactoe::set_bet_limits(RuntimeOrigin::root(), 10, Some(5)),
+			Error::<Test>::InvalidBetLimits
+		);
+		assert_ok!(Tictactoe::set_bet_limits(RuntimeOrigin::root(), 5, Some(20)));
+		System::assert_last_event((Event::BetLimitsSet { min_bet: 5, max_bet: Some(20) }).into());
+
+		assert_noop!(
+			Tictactoe::create_game(RuntimeOrigin::signed(1), 4, 1, 3, 3, None),
+			Error::<Test>::BetOutOfLimits
+		);
+		assert_noop!(
+			Tictactoe::create_game(RuntimeOrigin::signed(1), 21, 1, 3, 3, None),
+			Error::<Test>::BetOutOfLimits
+		);
+		// The fee is kept in the pallet account.
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_eq!(Balances::free_balance(&1), 100 - 10 - 2);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 10 + 2);
+	});
+}
+
+#[test]
+fn game_fee_and_bet_limits_apply_to_house_games_and_rematches() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account and house pot
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		assert_ok!(Balances::transfer(
+			RuntimeOrigin::signed(4),
+			Tictactoe::house_pot_account(),
+			50
+		));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Tictactoe::force_end_game(RuntimeOrigin::root(), 0, 1, 1));
+		assert_ok!(Tictactoe::offer_rematch(RuntimeOrigin::signed(1), 0));
+
+		assert_ok!(Tictactoe::set_game_fee(RuntimeOrigin::root(), 2));
+		assert_ok!(Tictactoe::set_bet_limits(RuntimeOrigin::root(), 15, Some(20)));
+
+		// The bet of a rematch must be within the limits set since the ended game.
+		assert_noop!(
+			Tictactoe::accept_rematch(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::BetOutOfLimits
+		);
+		assert_ok!(Tictactoe::set_bet_limits(RuntimeOrigin::root(), 5, Some(20)));
+		let host_balance = Balances::free_balance(&2);
+		assert_ok!(Tictactoe::accept_rematch(RuntimeOrigin::signed(2), 0));
+		assert_eq!(Balances::free_balance(&2), host_balance - 10 - 2);
+
+		// Games against the house too.
+		assert_noop!(
+			Tictactoe::create_vs_house(RuntimeOrigin::signed(3), 4, 0),
+			Error::<Test>::BetOutOfLimits
+		);
+		let host_balance = Balances::free_balance(&3);
+		assert_ok!(Tictactoe::create_vs_house(RuntimeOrigin::signed(3), 5, 0));
+		assert_eq!(Balances::free_balance(&3), host_balance - 5 - 2);
+	});
+}
+
+#[test]
+fn bracket_games_are_exempt_from_game_fee_and_bet_limits() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Tictactoe::set_game_fee(RuntimeOrigin::root(), 2));
+		assert_ok!(Tictactoe::set_bet_limits(RuntimeOrigin::root(), 5, Some(20)));
+		assert_ok!(Tictactoe::create_tournament(
+			RuntimeOrigin::signed(5),
+			1,
+			2,
+			vec![Permill::from_percent(100)],
+			Permill::zero()
+		));
+
+		// Players only pay the entry fee, the bracket game holds no bet.
+		let initial_balances = [1, 2].map(|player| Balances::free_balance(&player));
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(2), 0));
+		assert_eq!(Tictactoe::games(0).unwrap().bet, 0);
+		assert_eq!(Balances::free_balance(&1), initial_balances[0] - 1);
+		assert_eq!(Balances::free_balance(&2), initial_balances[1] - 1);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 2);
+	});
+}
+
 #[test]
 fn create_game_works() {
 	new_test_ext().execute_with(|| {
@@ -48,9 +193,20 @@ fn create_game_works() {
 		// Set safeguard deposit to check that is correctly transferred to pallet account
 		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
 		// Create game
-		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
 		// Check that event was emitted. First game created, so index is 0.
-		System::assert_last_event((Event::GameCreated { game_index: 0 }).into());
+		System::assert_last_event(
+			(Event::GameCreated {
+				game_index: 0,
+				host,
+				bet,
+				series_length: 1,
+				board_size: 3,
+				win_length: 3,
+				join_bonus: 0,
+			})
+			.into(),
+		);
 		// Check that host balance was correctly updated
 		assert_eq!(Balances::free_balance(&host), initial_balance - bet - safeguard_deposit);
 		// Check that game_index was correctly incremented.
@@ -71,11 +227,11 @@ fn create_game_fails_with_zero_bet() {
 		let initial_balance = Balances::free_balance(&host);
 		let bet = 0;
 		assert_noop!(
-			Tictactoe::create_game(RuntimeOrigin::signed(host), bet),
+			Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None),
 			Error::<Test>::CantBeZero
 		);
 		// Check that no state was changed after failed extrinsic.
-		assert!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet).is_err());
+		assert!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None).is_err());
 		assert_eq!(Balances::free_balance(&host), initial_balance);
 		assert_eq!(Tictactoe::game_index(), 0);
 		assert!(Tictactoe::games(0).is_none());
@@ -89,7 +245,7 @@ fn create_game_fails_insufficient_funds() {
 		let host = 1;
 		let initial_balance = Balances::free_balance(&host);
 		let bet = initial_balance + 1;
-		assert!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet).is_err());
+		assert!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None).is_err());
 	});
 }
 
@@ -104,12 +260,14 @@ fn join_a_game_works() {
 		let safeguard_deposit = 1;
 		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
 		// To join a game, it must be created first.
-		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
 
 		let initial_balance = Balances::free_balance(&joiner);
 		// Game id = 0 since first game created
 		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
-		System::assert_last_event((Event::PlayerJoined { game_index: 0, player: joiner }).into());
+		System::assert_last_event(
+			(Event::PlayerJoined { game_index: 0, player: joiner, bet, join_bonus: 0 }).into(),
+		);
 		// Check that storage and balances were correctly updated.
 		assert_eq!(Balances::free_balance(&joiner), initial_balance - bet - safeguard_deposit);
 		assert_eq!(Tictactoe::game_index(), 1);
@@ -143,7 +301,7 @@ fn join_a_full_game_fails() {
 		let joiner = 2;
 		let malicious_joiner = 3;
 		let bet = 10;
-		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
 		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
 		assert_noop!(
 			Tictactoe::join_game(RuntimeOrigin::signed(malicious_joiner), 0),
@@ -164,7 +322,7 @@ fn join_games_without_funds_fails() {
 			joiner_balance - 5
 		));
 		let bet = 10;
-		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
 		assert!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0).is_err());
 	});
 }
@@ -188,7 +346,7 @@ fn end_game_works() {
 
 		// To end a game, it must be created and joined first.
 		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
-		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
 		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
 
 		let host_init_balance = Balances::free_balance(&host);
@@ -208,7 +366,13 @@ fn end_game_works() {
 		// Joiner proposes same winner, since consensus is reached, game is ended.
 		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(joiner), 0, proposed_winner));
 		System::assert_last_event(
-			(Event::GameEnded { game_index: 0, winner: proposed_winner, jackpot: bet * 2 }).into(),
+			(Event::GameEnded {
+				game_index: 0,
+				winner: proposed_winner,
+				jackpot: bet * 2,
+				resolution: ResolutionKind::Agreement,
+			})
+			.into(),
 		);
 
 		// Check that balances and storage were correctly updated.
@@ -235,7 +399,7 @@ fn end_a_game_with_one_player_fails() {
 	new_test_ext().execute_with(|| {
 		let host = 1;
 		let bet: u64 = 10;
-		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
 		assert_noop!(
 			Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host),
 			Error::<Test>::BadAddress
@@ -263,7 +427,7 @@ fn mediation_is_applied() {
 		let safeguard_deposit = 1;
 
 		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
-		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
 		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
 
 		let host_proposed_winner = host;
@@ -273,7 +437,7 @@ fn mediation_is_applied() {
 		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host_proposed_winner));
 		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(joiner), 0, joiner_proposed_winner));
 		System::assert_last_event(
-			(Event::MediationRequested { game_index: 0, proposer: joiner }).into(),
+			(Event::MediationRequested { game_index: 0, winner: joiner, proposer: joiner }).into(),
 		);
 
 		assert_eq!(Balances::free_balance(&host), host_init_balance - bet - safeguard_deposit);
@@ -282,7 +446,20 @@ fn mediation_is_applied() {
 		// Assuming host was correct. Force end game on favor of host.
 		assert_ok!(Tictactoe::force_end_game(RuntimeOrigin::root(), 0, host_proposed_winner, host));
 		System::assert_last_event(
-			(Event::GameEnded { game_index: 0, winner: host_proposed_winner, jackpot: bet * 2 })
+			(Event::GameEnded {
+				game_index: 0,
+				winner: host_proposed_winner,
+				jackpot: bet * 2,
+				resolution: ResolutionKind::Forced,
+			})
+			.into(),
+		);
+		System::assert_has_event(
+			(Event::DepositReturned { game_index: 0, player: host, amount: safeguard_deposit })
+				.into(),
+		);
+		System::assert_has_event(
+			(Event::DepositSlashed { game_index: 0, player: joiner, amount: safeguard_deposit })
 				.into(),
 		);
 
@@ -295,6 +472,72 @@ fn mediation_is_applied() {
 	});
 }
 
+#[test]
+fn end_game_refunds_undecided_branches() {
+	new_test_ext().execute_with(|| {
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(3), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(4), 1));
+		let declared = crate::Call::<Test>::end_game { game_index: 0, winner: 1 }
+			.get_dispatch_info()
+			.weight;
+
+		// Proposing a winner and requesting mediation only charge their own weight.
+		let post_info = Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1).unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::end_game_first_proposal()));
+		assert!(<() as WeightInfo>::end_game_first_proposal().all_lt(declared));
+		let post_info = Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 2).unwrap();
+		assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::end_game_mismatch()));
+
+		// Deciding the game charges the declared weight.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(3), 1, 3));
+		let post_info = Tictactoe::end_game(RuntimeOrigin::signed(4), 1, 3).unwrap();
+		assert_eq!(post_info.actual_weight, None);
+	});
+}
+
+#[test]
+fn simulate_end_game_predicts_without_changing_state() {
+	new_test_ext().execute_with(|| {
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		let (host, joiner, bet, safeguard_deposit) = (1, 2, 10, 1);
+		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+
+		assert_eq!(
+			Tictactoe::simulate_end_game(1, host, host),
+			EndGameOutcome::Failed(Error::<Test>::GameDoesNotExist.into())
+		);
+		assert_eq!(
+			Tictactoe::simulate_end_game(0, 3, host),
+			EndGameOutcome::Failed(Error::<Test>::NotAPlayer.into())
+		);
+		assert_eq!(Tictactoe::simulate_end_game(0, host, host), EndGameOutcome::ProposalRecorded);
+		assert_eq!(Tictactoe::games(0).unwrap().handshake(), &(None, None));
+
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host));
+		assert_eq!(
+			Tictactoe::simulate_end_game(0, joiner, joiner),
+			EndGameOutcome::MediationNeeded
+		);
+		let host_balance = Balances::free_balance(host);
+		assert_eq!(
+			Tictactoe::simulate_end_game(0, joiner, host),
+			EndGameOutcome::Settled {
+				winner: host,
+				payouts: vec![(host, 2 * bet + safeguard_deposit), (joiner, safeguard_deposit)],
+			}
+		);
+		assert_eq!(Balances::free_balance(host), host_balance);
+		assert_eq!(Tictactoe::games(0).unwrap().status(), GameStatus::Open);
+	});
+}
+
 #[test]
 fn invalid_accounts_fail_to_end() {
 	new_test_ext().execute_with(|| {
@@ -302,7 +545,7 @@ fn invalid_accounts_fail_to_end() {
 		let joiner = 2;
 		let invalid_account = 3;
 		let bet: u64 = 10;
-		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
 		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
 		assert_noop!(
 			Tictactoe::end_game(RuntimeOrigin::signed(invalid_account), 0, host),
@@ -321,7 +564,7 @@ fn non_sudo_cant_force_end() {
 		let host = 1;
 		let joiner = 2;
 		let bet: u64 = 10;
-		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
 		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
 		assert!(Tictactoe::force_end_game(RuntimeOrigin::signed(host), 0, host, host).is_err());
 	});
@@ -363,3 +606,2076 @@ fn non_sudo_cant_withdraw() {
 			.is_err());
 	});
 }
+
+#[test]
+fn create_tournament_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let organizer = 5;
+		let entry_fee = 10;
+		let prize_distribution = vec![Permill::from_percent(50), Permill::from_percent(25)];
+		let organizer_cut = Permill::from_percent(10);
+
+		assert_ok!(Tictactoe::create_tournament(
+			RuntimeOrigin::signed(organizer),
+			entry_fee,
+			4,
+			prize_distribution.clone(),
+			organizer_cut
+		));
+		System::assert_last_event(
+			(Event::TournamentCreated { tournament_index: 0, organizer }).into(),
+		);
+		assert_eq!(Tictactoe::tournament_index(), 1);
+
+		let tournament = Tictactoe::tournaments(0).unwrap();
+		assert_eq!(tournament.organizer, organizer);
+		assert_eq!(tournament.entry_fee, entry_fee);
+		assert_eq!(tournament.max_players, 4);
+		assert_eq!(tournament.prize_distribution.to_vec(), prize_distribution);
+		assert_eq!(tournament.organizer_cut, organizer_cut);
+		assert_eq!(tournament.status, TournamentStatus::Registering);
+		// Creating a tournament doesn't move funds.
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 0);
+	});
+}
+
+#[test]
+fn create_tournament_fails_with_invalid_parameters() {
+	new_test_ext().execute_with(|| {
+		let organizer = 5;
+		let prize_distribution = vec![Permill::from_percent(100)];
+		assert_noop!(
+			Tictactoe::create_tournament(
+				RuntimeOrigin::signed(organizer),
+				0,
+				4,
+				prize_distribution.clone(),
+				Permill::zero()
+			),
+			Error::<Test>::CantBeZero
+		);
+		// Bracket size must be a power of two and within `MaxTournamentPlayers`.
+		for max_players in [0, 1, 3, 16] {
+			assert_noop!(
+				Tictactoe::create_tournament(
+					RuntimeOrigin::signed(organizer),
+					10,
+					max_players,
+					prize_distribution.clone(),
+					Permill::zero()
+				),
+				Error::<Test>::InvalidTournamentSize
+			);
+		}
+		// Prize distribution can't be empty nor hand out more than the pool.
+		for prize_distribution in
+			[vec![], vec![Permill::from_percent(60), Permill::from_percent(50)]]
+		{
+			assert_noop!(
+				Tictactoe::create_tournament(
+					RuntimeOrigin::signed(organizer),
+					10,
+					4,
+					prize_distribution,
+					Permill::zero()
+				),
+				Error::<Test>::InvalidPrizeDistribution
+			);
+		}
+	});
+}
+
+#[test]
+fn full_bracket_starts_first_round() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let entry_fee = 10;
+		assert_ok!(Tictactoe::create_tournament(
+			RuntimeOrigin::signed(5),
+			entry_fee,
+			4,
+			vec![Permill::from_percent(100)],
+			Permill::zero()
+		));
+
+		let initial_balance = Balances::free_balance(&1);
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(
+			(Event::TournamentPlayerRegistered { tournament_index: 0, player: 1 }).into(),
+		);
+		assert_eq!(Balances::free_balance(&1), initial_balance - entry_fee);
+		assert_noop!(
+			Tictactoe::register_for_tournament(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::AlreadyRegistered
+		);
+		// No games are created until the bracket is full.
+		assert!(Tictactoe::games(0).is_none());
+
+		for player in 2..=4 {
+			assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(player), 0));
+		}
+		System::assert_last_event(
+			(Event::TournamentRoundStarted { tournament_index: 0, round: 1 }).into(),
+		);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), entry_fee * 4);
+
+		// Players are paired in registration order.
+		assert_eq!(Tictactoe::game_index(), 2);
+		assert_eq!(Tictactoe::games(0).unwrap().payout_addresses, (Some(1), Some(2)));
+		assert_eq!(Tictactoe::games(1).unwrap().payout_addresses, (Some(3), Some(4)));
+		assert_eq!(Tictactoe::games(0).unwrap().tournament, Some(0));
+		assert_eq!(Tictactoe::games(0).unwrap().bet, 0);
+
+		let tournament = Tictactoe::tournaments(0).unwrap();
+		assert_eq!(tournament.status, TournamentStatus::InProgress);
+		assert_eq!(tournament.round, 1);
+		assert_eq!(tournament.round_games.to_vec(), vec![0, 1]);
+
+		assert_noop!(
+			Tictactoe::register_for_tournament(RuntimeOrigin::signed(5), 0),
+			Error::<Test>::TournamentNotRegistering
+		);
+		// Bracket games can't be joined.
+		assert_noop!(Tictactoe::join_game(RuntimeOrigin::signed(5), 0), Error::<Test>::GameFull);
+	});
+}
+
+#[test]
+fn tournament_pays_prizes_when_final_ends() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let organizer = 5;
+		// Fund pallet account
+		let pallet_funding = 50;
+		assert_ok!(Balances::transfer(
+			RuntimeOrigin::signed(organizer),
+			Tictactoe::account_id(),
+			pallet_funding
+		));
+
+		let entry_fee = 10;
+		assert_ok!(Tictactoe::create_tournament(
+			RuntimeOrigin::signed(organizer),
+			entry_fee,
+			4,
+			vec![Permill::from_percent(50), Permill::from_percent(25)],
+			Permill::from_percent(10)
+		));
+		for player in 1..=4 {
+			assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(player), 0));
+		}
+		let initial_balances: Vec<u64> = (1..=5).map(|who| Balances::free_balance(&who)).collect();
+
+		// First round: 1 beats 2 and 3 beats 4.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 1));
+		System::assert_last_event(
+			(Event::GameEnded {
+				game_index: 0,
+				winner: 1,
+				jackpot: 0,
+				resolution: ResolutionKind::Agreement,
+			})
+			.into(),
+		);
+		assert_eq!(Tictactoe::tournaments(0).unwrap().round, 1);
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(3), 1, 3));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(4), 1, 3));
+		System::assert_last_event(
+			(Event::TournamentRoundStarted { tournament_index: 0, round: 2 }).into(),
+		);
+
+		// Final between round winners.
+		assert_eq!(Tictactoe::games(2).unwrap().payout_addresses, (Some(1), Some(3)));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 2, 3));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(3), 2, 3));
+
+		let prize_pool = entry_fee * 4;
+		System::assert_last_event(
+			(Event::TournamentEnded { tournament_index: 0, champion: 3, prize_pool }).into(),
+		);
+		assert_eq!(Tictactoe::tournaments(0).unwrap().status, TournamentStatus::Finished);
+
+		// Organizer takes 10% of the pool, the finalist gets 25% of the remaining prizes and
+		// the champion gets the rest.
+		let organizer_cut = 4;
+		let prizes = prize_pool - organizer_cut;
+		assert_eq!(Balances::free_balance(&1), initial_balances[0] + 9);
+		assert_eq!(Balances::free_balance(&2), initial_balances[1]);
+		assert_eq!(Balances::free_balance(&3), initial_balances[2] + prizes - 9);
+		assert_eq!(Balances::free_balance(&4), initial_balances[3]);
+		assert_eq!(Balances::free_balance(&organizer), initial_balances[4] + organizer_cut);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), pallet_funding);
+	});
+}
+
+#[test]
+fn force_end_game_advances_tournament() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Tictactoe::create_tournament(
+			RuntimeOrigin::signed(5),
+			10,
+			2,
+			vec![Permill::from_percent(100)],
+			Permill::zero()
+		));
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(2), 0));
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 50));
+
+		// Players disagree on the final.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 2));
+		System::assert_last_event(
+			(Event::MediationRequested { game_index: 0, winner: 2, proposer: 2 }).into(),
+		);
+
+		// Winner of a bracket game must be one of its players.
+		assert_noop!(
+			Tictactoe::force_end_game(RuntimeOrigin::root(), 0, 3, 1),
+			Error::<Test>::NotAPlayer
+		);
+
+		let initial_balance = Balances::free_balance(&2);
+		assert_ok!(Tictactoe::force_end_game(RuntimeOrigin::root(), 0, 2, 2));
+		System::assert_last_event(
+			(Event::TournamentEnded { tournament_index: 0, champion: 2, prize_pool: 20 }).into(),
+		);
+		assert_eq!(Balances::free_balance(&2), initial_balance + 20);
+	});
+}
+
+#[test]
+fn stalled_bracket_game_is_timed_out() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Tictactoe::create_tournament(
+			RuntimeOrigin::signed(5),
+			10,
+			2,
+			vec![Permill::from_percent(100)],
+			Permill::zero()
+		));
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(2), 0));
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 50));
+
+		// The first mover has `MoveTimeout` blocks to open the board.
+		assert_eq!(Tictactoe::move_deadlines(0), Some(11));
+		System::set_block_number(11);
+		assert_noop!(
+			Tictactoe::claim_timeout(RuntimeOrigin::none(), 0),
+			Error::<Test>::NotTimedOut
+		);
+
+		// Nobody played, the host loses the final by timeout.
+		System::set_block_number(12);
+		let initial_balance = Balances::free_balance(&2);
+		assert_ok!(Tictactoe::claim_timeout(RuntimeOrigin::none(), 0));
+		System::assert_last_event(
+			(Event::TournamentEnded { tournament_index: 0, champion: 2, prize_pool: 20 }).into(),
+		);
+		assert_eq!(Balances::free_balance(&2), initial_balance + 20);
+	});
+}
+
+#[test]
+fn bracket_games_count_as_open_games() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MaxOpenGames::set(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(3), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::create_tournament(
+			RuntimeOrigin::signed(5),
+			10,
+			2,
+			vec![Permill::from_percent(100)],
+			Permill::zero()
+		));
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(1), 0));
+
+		// The bracket can't start while `MaxOpenGames` games are open.
+		assert_noop!(
+			Tictactoe::register_for_tournament(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::TooManyOpenGames
+		);
+		assert_ok!(Tictactoe::cancel_game(RuntimeOrigin::signed(3), 0));
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(2), 0));
+		assert_eq!(Tictactoe::open_games(), 1);
+	});
+}
+
+#[test]
+fn bracket_rounds_ignore_games_opened_meanwhile() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MaxOpenGames::set(4);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		assert_ok!(Tictactoe::create_tournament(
+			RuntimeOrigin::signed(5),
+			10,
+			4,
+			vec![Permill::from_percent(100)],
+			Permill::zero()
+		));
+		for player in 1..=4 {
+			assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(player), 0));
+		}
+		// The three bracket games are reserved as soon as the bracket fills.
+		assert_eq!(Tictactoe::open_games(), 3);
+
+		// Other games fill `MaxOpenGames` whenever a bracket game ends.
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(5), 5, 1, 3, 3, None));
+		assert_noop!(
+			Tictactoe::create_game(RuntimeOrigin::signed(5), 5, 1, 3, 3, None),
+			Error::<Test>::TooManyOpenGames
+		);
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 1));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(5), 5, 1, 3, 3, None));
+		assert_eq!(Tictactoe::open_games(), 4);
+
+		// Finishing the round still starts the final.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(3), 1, 3));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(4), 1, 3));
+		System::assert_last_event(
+			(Event::TournamentRoundStarted { tournament_index: 0, round: 2 }).into(),
+		);
+		assert_eq!(Tictactoe::open_games(), 3);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(5), 5, 1, 3, 3, None));
+
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 4, 3));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(3), 4, 3));
+		System::assert_last_event(
+			(Event::TournamentEnded { tournament_index: 0, champion: 3, prize_pool: 40 }).into(),
+		);
+		assert_eq!(Tictactoe::open_games(), 3);
+	});
+}
+
+#[test]
+fn cancel_tournament_refunds_entry_fees() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let organizer = 5;
+		let entry_fee = 10;
+		assert_ok!(Tictactoe::create_tournament(
+			RuntimeOrigin::signed(organizer),
+			entry_fee,
+			4,
+			vec![Permill::from_percent(100)],
+			Permill::zero()
+		));
+		let initial_balance = Balances::free_balance(&1);
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(1), 0));
+		assert_ok!(Tictactoe::register_for_tournament(RuntimeOrigin::signed(2), 0));
+
+		assert_noop!(
+			Tictactoe::cancel_tournament(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::NotTheOrganizer
+		);
+		// Keep pallet account alive after refunds.
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(3), Tictactoe::account_id(), 50));
+
+		assert_ok!(Tictactoe::cancel_tournament(RuntimeOrigin::signed(organizer), 0));
+		System::assert_last_event((Event::TournamentCancelled { tournament_index: 0 }).into());
+		assert_eq!(Balances::free_balance(&1), initial_balance);
+		assert_eq!(Tictactoe::tournaments(0).unwrap().status, TournamentStatus::Cancelled);
+		assert_noop!(
+			Tictactoe::register_for_tournament(RuntimeOrigin::signed(3), 0),
+			Error::<Test>::TournamentNotRegistering
+		);
+	});
+}
+
+#[test]
+fn create_game_fails_with_invalid_series_length() {
+	new_test_ext().execute_with(|| {
+		let host = 1;
+		let bet = 10;
+		// Series must be odd so a majority always exists, and within `MaxSeriesLength`.
+		for series_length in [0, 2, 7] {
+			assert_noop!(
+				Tictactoe::create_game(RuntimeOrigin::signed(host), bet, series_length, 3, 3, None),
+				Error::<Test>::InvalidSeriesLength
+			);
+		}
+	});
+}
+
+#[test]
+fn series_pays_jackpot_once_majority_is_reached() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		//Fund pallet account
+		let pallet_funding = 50;
+		assert_ok!(Balances::transfer(
+			RuntimeOrigin::signed(3),
+			Tictactoe::account_id(),
+			pallet_funding
+		));
+
+		let host = 1;
+		let joiner = 2;
+		let bet: u64 = 10;
+		let safeguard_deposit = 1;
+		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 3, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+		let host_init_balance = Balances::free_balance(&host);
+		let joiner_init_balance = Balances::free_balance(&joiner);
+		assert_eq!(Tictactoe::games(0).unwrap().first_mover(), Some(host));
+
+		// Host takes the first round, handshake is reset and joiner moves first next.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(joiner), 0, host));
+		System::assert_last_event(
+			(Event::RoundWon {
+				game_index: 0,
+				winner: host,
+				round_wins: (1, 0),
+				resolution: ResolutionKind::Agreement,
+			})
+			.into(),
+		);
+		let game = Tictactoe::games(0).unwrap();
+		assert_eq!(game.ended, false);
+		assert_eq!(game.handshake, (None, None));
+		assert_eq!(game.first_mover(), Some(joiner));
+
+		// Joiner takes the second round.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(joiner), 0, joiner));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, joiner));
+		System::assert_last_event(
+			(Event::RoundWon {
+				game_index: 0,
+				winner: joiner,
+				round_wins: (1, 1),
+				resolution: ResolutionKind::Agreement,
+			})
+			.into(),
+		);
+		assert_eq!(Tictactoe::games(0).unwrap().first_mover(), Some(host));
+		// Escrow is held until the series is decided.
+		assert_eq!(Balances::free_balance(&host), host_init_balance);
+		assert_eq!(Balances::free_balance(&joiner), joiner_init_balance);
+
+		// Host takes the decider and the jackpot.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(joiner), 0, host));
+		System::assert_last_event(
+			(Event::GameEnded {
+				game_index: 0,
+				winner: host,
+				jackpot: bet * 2,
+				resolution: ResolutionKind::Agreement,
+			})
+			.into(),
+		);
+		assert_eq!(Tictactoe::games(0).unwrap().ended, true);
+		assert_eq!(Tictactoe::games(0).unwrap().round_wins, (2, 1));
+		assert_eq!(Balances::free_balance(&host), host_init_balance + safeguard_deposit + bet * 2);
+		assert_eq!(Balances::free_balance(&joiner), joiner_init_balance + safeguard_deposit);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), pallet_funding);
+	});
+}
+
+#[test]
+fn disputed_series_round_is_settled_by_mediation() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		//Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(3), Tictactoe::account_id(), 50));
+
+		let host = 1;
+		let joiner = 2;
+		let bet: u64 = 10;
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 5, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+		let joiner_init_balance = Balances::free_balance(&joiner);
+
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(joiner), 0, host));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(joiner), 0, joiner));
+		System::assert_last_event(
+			(Event::MediationRequested { game_index: 0, winner: joiner, proposer: joiner }).into(),
+		);
+
+		// Mediator settles the whole series.
+		assert_ok!(Tictactoe::force_end_game(RuntimeOrigin::root(), 0, joiner, joiner));
+		assert_eq!(Tictactoe::games(0).unwrap().ended, true);
+		assert_eq!(Balances::free_balance(&joiner), joiner_init_balance + bet * 2);
+	});
+}
+
+#[test]
+fn place_side_bet_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (host, joiner, bettor) = (1, 2, 3);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), 10, 1, 3, 3, None));
+
+		// Side bets open once the game has been joined.
+		assert_noop!(
+			Tictactoe::place_side_bet(RuntimeOrigin::signed(bettor), 0, true, 10),
+			Error::<Test>::GameNotJoined
+		);
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+
+		assert_noop!(
+			Tictactoe::place_side_bet(RuntimeOrigin::signed(host), 0, true, 10),
+			Error::<Test>::PlayersCantSideBet
+		);
+		assert_noop!(
+			Tictactoe::place_side_bet(RuntimeOrigin::signed(bettor), 0, true, 0),
+			Error::<Test>::CantBeZero
+		);
+
+		let initial_balance = Balances::free_balance(&bettor);
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(bettor), 0, true, 10));
+		System::assert_last_event(
+			(Event::SideBetPlaced { game_index: 0, bettor, on_host: true, amount: 10 }).into(),
+		);
+		assert_eq!(Balances::free_balance(&bettor), initial_balance - 10);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 30);
+
+		// Pool is bounded by `MaxSideBetsPerGame`.
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(4), 0, false, 10));
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(5), 0, false, 10));
+		assert_noop!(
+			Tictactoe::place_side_bet(RuntimeOrigin::signed(bettor), 0, true, 10),
+			Error::<Test>::TooManySideBets
+		);
+		assert_eq!(Tictactoe::side_bets(0).len(), 3);
+	});
+}
+
+#[test]
+fn side_bets_close_once_a_winner_is_proposed() {
+	new_test_ext().execute_with(|| {
+		let (host, joiner) = (1, 2);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host));
+		assert_noop!(
+			Tictactoe::place_side_bet(RuntimeOrigin::signed(3), 0, true, 10),
+			Error::<Test>::SideBetsClosed
+		);
+	});
+}
+
+#[test]
+fn side_bets_are_shared_among_winner_backers() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (host, joiner) = (1, 2);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 50));
+
+		let initial_balances = [3, 4, 5].map(|bettor| Balances::free_balance(&bettor));
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(3), 0, true, 10));
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(4), 0, true, 30));
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(5), 0, false, 20));
+
+		// Host wins, the pool of 60 is shared among host backers in proportion to their stake.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(joiner), 0, host));
+		System::assert_has_event(
+			(Event::SideBetsSettled { game_index: 0, host_won: true, total_pool: 60 }).into(),
+		);
+		assert_eq!(Balances::free_balance(&3), initial_balances[0] + 5);
+		assert_eq!(Balances::free_balance(&4), initial_balances[1] + 15);
+		assert_eq!(Balances::free_balance(&5), initial_balances[2] - 20);
+		assert!(Tictactoe::side_bets(0).is_empty());
+	});
+}
+
+#[test]
+fn side_bets_pay_rounding_remainder_to_first_winner_backer() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (host, joiner) = (1, 2);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 50));
+
+		let initial_balances = [3, 4, 5].map(|bettor| Balances::free_balance(&bettor));
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(3), 0, true, 1));
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(4), 0, true, 2));
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(5), 0, false, 2));
+
+		// Shares of the pool of 5 are 5/3 and 10/3, rounded down to 1 and 3. The remaining 1 goes
+		// to the first backer of the host.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(joiner), 0, host));
+		assert_eq!(Balances::free_balance(&3), initial_balances[0] + 1);
+		assert_eq!(Balances::free_balance(&4), initial_balances[1] + 1);
+		assert_eq!(Balances::free_balance(&5), initial_balances[2] - 2);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 50);
+	});
+}
+
+#[test]
+fn side_bets_are_refunded_when_nobody_backed_the_winner() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (host, joiner) = (1, 2);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 50));
+
+		let initial_balances = [3, 4].map(|bettor| Balances::free_balance(&bettor));
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(3), 0, false, 10));
+		assert_ok!(Tictactoe::place_side_bet(RuntimeOrigin::signed(4), 0, false, 20));
+
+		assert_ok!(Tictactoe::force_end_game(RuntimeOrigin::root(), 0, host, host));
+		System::assert_has_event((Event::SideBetsRefunded { game_index: 0 }).into());
+		assert_eq!(Balances::free_balance(&3), initial_balances[0]);
+		assert_eq!(Balances::free_balance(&4), initial_balances[1]);
+	});
+}
+
+#[test]
+fn play_move_follows_turns() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (host, joiner) = (1, 2);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), 10, 1, 3, 3, None));
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(host), 0, (1, 1)),
+			Error::<Test>::GameNotJoined
+		);
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+
+		// Host opens the board.
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(joiner), 0, (1, 1)),
+			Error::<Test>::NotYourTurn
+		);
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(3), 0, (1, 1)),
+			Error::<Test>::NotAPlayer
+		);
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(host), 0, (1, 1)));
+		System::assert_last_event(
+			(Event::MovePlayed { game_index: 0, player: host, game_move: (1, 1) }).into(),
+		);
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(host), 0, (0, 0)),
+			Error::<Test>::NotYourTurn
+		);
+
+		// Moves must be allowed by the rules.
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(joiner), 0, (1, 1)),
+			Error::<Test>::InvalidMove
+		);
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(joiner), 0, (3, 0)),
+			Error::<Test>::InvalidMove
+		);
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(joiner), 0, (0, 0)));
+		assert_eq!(<Test as Config>::Rules::moves_played(&Tictactoe::boards(0).unwrap()), 2);
+	});
+}
+
+#[test]
+fn play_move_fails_once_a_winner_is_proposed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (host, joiner) = (1, 2);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(host), 0, (1, 1)));
+
+		// The round is settled through the handshake once a winner is proposed.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(host), 0, host));
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(joiner), 0, (0, 0)),
+			Error::<Test>::WinnerProposed
+		);
+
+		// Nor can moves sidestep the mediation.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(joiner), 0, joiner));
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(joiner), 0, (0, 0)),
+			Error::<Test>::WinnerProposed
+		);
+		assert_eq!(Tictactoe::games(0).unwrap().handshake, (Some(host), Some(joiner)));
+	});
+}
+
+#[test]
+fn winning_board_ends_the_game() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (host, joiner) = (1, 2);
+		let bet = 10;
+		let initial_balance = Balances::free_balance(&host);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 50));
+
+		// Host takes the top row, no handshake is needed.
+		for (player, cell) in
+			[(host, (0, 0)), (joiner, (1, 0)), (host, (0, 1)), (joiner, (1, 1)), (host, (0, 2))]
+		{
+			assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(player), 0, cell));
+		}
+		System::assert_last_event(
+			(Event::GameEnded {
+				game_index: 0,
+				winner: host,
+				jackpot: 2 * bet,
+				resolution: ResolutionKind::Board,
+			})
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&host), initial_balance + bet);
+		assert!(Tictactoe::games(0).unwrap().ended);
+		assert!(Tictactoe::boards(0).is_none());
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(joiner), 0, (1, 2)),
+			Error::<Test>::GameAlreadyEnded
+		);
+	});
+}
+
+#[test]
+fn drawn_board_is_replayed() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (host, joiner) = (1, 2);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+
+		// Cells in row-major order.
+		let moves = [0, 1, 2, 4, 7, 5, 3, 6, 8];
+		for (turn, cell) in moves.into_iter().enumerate() {
+			let player = if turn % 2 == 0 { host } else { joiner };
+			assert_ok!(Tictactoe::play_move(
+				RuntimeOrigin::signed(player),
+				0,
+				(cell / 3, cell % 3)
+			));
+		}
+		System::assert_last_event((Event::RoundDrawn { game_index: 0 }).into());
+		assert!(Tictactoe::boards(0).is_none());
+		assert!(!Tictactoe::games(0).unwrap().ended);
+		assert_eq!(Tictactoe::games(0).unwrap().round_wins, (0, 0));
+
+		// The round is replayed on a fresh board.
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(host), 0, (1, 1)));
+	});
+}
+
+#[test]
+fn tic_tac_toe_rules_detect_lines() {
+	assert!(TicTacToe::new_board(5, 4).is_none());
+	let mut board = TicTacToe::new_board(3, 3).unwrap();
+	for cell in [2, 4] {
+		assert_ok!(TicTacToe::apply_move(&mut board, Side::Joiner, &cell));
+	}
+	assert_eq!(TicTacToe::outcome(&board), BoardOutcome::InProgress);
+	assert_eq!(TicTacToe::apply_move(&mut board, Side::Host, &4), Err(MoveError::CellTaken));
+	assert_eq!(TicTacToe::apply_move(&mut board, Side::Host, &9), Err(MoveError::OutOfBounds));
+
+	assert_ok!(TicTacToe::apply_move(&mut board, Side::Joiner, &6));
+	assert_eq!(TicTacToe::outcome(&board), BoardOutcome::Won(Side::Joiner));
+	assert!(TicTacToe::legal_moves(&board).is_empty());
+	assert_eq!(TicTacToe::apply_move(&mut board, Side::Host, &0), Err(MoveError::BoardDecided));
+}
+
+#[test]
+fn k_in_a_row_rules_detect_lines() {
+	type Gomoku = KInARow<ConstU32<5>>;
+	// Win length must be between 3 and the board size, which is bounded.
+	assert!(Gomoku::new_board(6, 4).is_none());
+	assert!(Gomoku::new_board(4, 5).is_none());
+	assert!(Gomoku::new_board(5, 2).is_none());
+	let mut board = Gomoku::new_board(5, 4).unwrap();
+	assert_eq!(Gomoku::legal_moves(&board).len(), 25);
+	assert_eq!(Gomoku::apply_move(&mut board, Side::Host, &(5, 0)), Err(MoveError::OutOfBounds));
+
+	for cell in [(0, 1), (1, 2), (2, 3)] {
+		assert_ok!(Gomoku::apply_move(&mut board, Side::Host, &cell));
+	}
+	// Three in a row is not enough.
+	assert_eq!(Gomoku::outcome(&board), BoardOutcome::InProgress);
+	assert_eq!(Gomoku::moves_played(&board), 3);
+
+	assert_ok!(Gomoku::apply_move(&mut board, Side::Host, &(3, 4)));
+	assert_eq!(Gomoku::outcome(&board), BoardOutcome::Won(Side::Host));
+}
+
+#[test]
+fn create_game_fails_with_invalid_board_size() {
+	new_test_ext().execute_with(|| {
+		let host = 1;
+		let bet = 10;
+		// Boards are bounded by `MaxBoardSize` and the win length must fit in the board.
+		for (board_size, win_length) in [(6, 4), (4, 5), (3, 2), (0, 0)] {
+			assert_noop!(
+				Tictactoe::create_game(
+					RuntimeOrigin::signed(host),
+					bet,
+					1,
+					board_size,
+					win_length,
+					None
+				),
+				Error::<Test>::InvalidBoardSize
+			);
+		}
+	});
+}
+
+#[test]
+fn larger_board_needs_a_longer_line() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (host, joiner) = (1, 2);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), 10, 1, 5, 4, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), 0));
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 50));
+
+		// Three in a row doesn't win a 5x5 board with four in a row.
+		for (player, cell) in
+			[(host, (2, 0)), (joiner, (0, 0)), (host, (2, 1)), (joiner, (0, 1)), (host, (2, 2))]
+		{
+			assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(player), 0, cell));
+		}
+		assert!(!Tictactoe::games(0).unwrap().ended);
+
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(joiner), 0, (0, 2)));
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(host), 0, (2, 3)));
+		System::assert_last_event(
+			(Event::GameEnded {
+				game_index: 0,
+				winner: host,
+				jackpot: 20,
+				resolution: ResolutionKind::Board,
+			})
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn fund_house_pot_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 50));
+		assert!(Tictactoe::fund_house_pot(RuntimeOrigin::signed(5), 40).is_err());
+
+		assert_ok!(Tictactoe::fund_house_pot(RuntimeOrigin::root(), 40));
+		System::assert_last_event((Event::HousePotFunded { amount: 40 }).into());
+		assert_eq!(Balances::free_balance(Tictactoe::house_pot_account()), 40);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 10);
+	});
+}
+
+#[test]
+fn create_vs_house_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let player = 1;
+		let bet = 10;
+		let house = Tictactoe::house_pot_account();
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), house, 50));
+
+		assert_noop!(
+			Tictactoe::create_vs_house(RuntimeOrigin::signed(player), bet, 5),
+			Error::<Test>::InvalidDifficulty
+		);
+		assert_ok!(Tictactoe::create_vs_house(RuntimeOrigin::signed(player), bet, 2));
+		System::assert_last_event(
+			(Event::PlayerJoined { game_index: 0, player: house, bet, join_bonus: 0 }).into(),
+		);
+		assert_eq!(Tictactoe::games(0).unwrap().payout_addresses, (Some(player), Some(house)));
+		assert_eq!(Balances::free_balance(house), 50 - bet);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 2 * bet);
+
+		// Games against the house are not decided through the handshake.
+		assert_noop!(
+			Tictactoe::end_game(RuntimeOrigin::signed(player), 0, player),
+			Error::<Test>::HouseGameIsPlayedOnChain
+		);
+	});
+}
+
+#[test]
+fn idle_house_game_is_timed_out() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (player, bet) = (1, 10);
+		let house = Tictactoe::house_pot_account();
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), house, 50));
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(4), Tictactoe::account_id(), 50));
+		assert_ok!(Tictactoe::create_vs_house(RuntimeOrigin::signed(player), bet, 0));
+
+		// The host never opens the board, the house takes the stake once the deadline passes.
+		assert_eq!(Tictactoe::move_deadlines(0), Some(11));
+		System::set_block_number(12);
+		assert_ok!(Tictactoe::claim_timeout(RuntimeOrigin::none(), 0));
+		System::assert_has_event((Event::TimeoutClaimed { game_index: 0, winner: house }).into());
+		assert!(Tictactoe::games(0).unwrap().ended);
+		assert_eq!(Balances::free_balance(house), 50 + bet);
+		assert_eq!(Tictactoe::open_games(), 0);
+	});
+}
+
+#[test]
+fn house_exposure_is_capped() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Balances::transfer(
+			RuntimeOrigin::signed(5),
+			Tictactoe::house_pot_account(),
+			90
+		));
+
+		// Capped per game.
+		assert_noop!(
+			Tictactoe::create_vs_house(RuntimeOrigin::signed(1), 21, 0),
+			Error::<Test>::HouseExposureExceeded
+		);
+		// Capped per block.
+		assert_ok!(Tictactoe::create_vs_house(RuntimeOrigin::signed(1), 20, 0));
+		assert_noop!(
+			Tictactoe::create_vs_house(RuntimeOrigin::signed(2), 20, 0),
+			Error::<Test>::HouseExposureExceeded
+		);
+		assert_ok!(Tictactoe::create_vs_house(RuntimeOrigin::signed(2), 10, 0));
+
+		System::set_block_number(2);
+		assert_ok!(Tictactoe::create_vs_house(RuntimeOrigin::signed(3), 20, 0));
+	});
+}
+
+#[test]
+fn house_replies_in_the_same_call() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let player = 1;
+		let bet = 10;
+		let house = Tictactoe::house_pot_account();
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), house, 50));
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		assert_ok!(Tictactoe::create_vs_house(RuntimeOrigin::signed(player), bet, 2));
+
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(player), 0, (2, 2)));
+		System::assert_last_event(
+			(Event::MovePlayed { game_index: 0, player: house, game_move: (0, 0) }).into(),
+		);
+		// House blocks the bottom row.
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(player), 0, (2, 1)));
+		System::assert_last_event(
+			(Event::MovePlayed { game_index: 0, player: house, game_move: (2, 0) }).into(),
+		);
+		// House completes the left column.
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(player), 0, (1, 2)));
+		System::assert_has_event(
+			(Event::MovePlayed { game_index: 0, player: house, game_move: (1, 0) }).into(),
+		);
+		System::assert_last_event(
+			(Event::GameEnded {
+				game_index: 0,
+				winner: house,
+				jackpot: 2 * bet,
+				resolution: ResolutionKind::Board,
+			})
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(house), 50 + bet);
+	});
+}
+
+#[test]
+fn house_can_be_beaten() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let player = 1;
+		let bet = 10;
+		let initial_balance = Balances::free_balance(&player);
+		assert_ok!(Balances::transfer(
+			RuntimeOrigin::signed(5),
+			Tictactoe::house_pot_account(),
+			50
+		));
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+
+		// At the lowest difficulty the house doesn't look ahead and misses the left column.
+		assert_ok!(Tictactoe::create_vs_house(RuntimeOrigin::signed(player), bet, 0));
+		for cell in [(0, 0), (1, 0), (2, 0)] {
+			assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(player), 0, cell));
+		}
+		System::assert_last_event(
+			(Event::GameEnded {
+				game_index: 0,
+				winner: player,
+				jackpot: 2 * bet,
+				resolution: ResolutionKind::Board,
+			})
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&player), initial_balance + bet);
+	});
+}
+
+/// Create a 3x3 game between 1 and 2 where each player proposed themselves as the winner.
+fn create_disputed_game() {
+	assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+	assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+	assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1));
+	assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 2));
+}
+
+#[test]
+fn submit_transcript_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let transcript = vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)];
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1));
+		assert_noop!(
+			Tictactoe::submit_transcript(
+				RuntimeOrigin::signed(1),
+				0,
+				transcript.clone().try_into().unwrap()
+			),
+			Error::<Test>::NotInMediation
+		);
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 2));
+
+		assert_noop!(
+			Tictactoe::submit_transcript(
+				RuntimeOrigin::signed(3),
+				0,
+				transcript.clone().try_into().unwrap()
+			),
+			Error::<Test>::NotAPlayer
+		);
+		assert_ok!(Tictactoe::submit_transcript(
+			RuntimeOrigin::signed(1),
+			0,
+			transcript.clone().try_into().unwrap()
+		));
+		System::assert_last_event((Event::TranscriptSubmitted { game_index: 0, player: 1 }).into());
+		assert!(Tictactoe::transcript_hashes(0).0.is_some());
+		assert!(Tictactoe::transcript_hashes(0).1.is_none());
+		assert_noop!(
+			Tictactoe::submit_transcript(
+				RuntimeOrigin::signed(1),
+				0,
+				transcript.try_into().unwrap()
+			),
+			Error::<Test>::TranscriptAlreadySubmitted
+		);
+	});
+}
+
+#[test]
+fn resolve_dispute_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bet = 10;
+		let initial_balance = Balances::free_balance(&1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		create_disputed_game();
+
+		// Host completes the top row.
+		let transcript = vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)];
+		for player in [1, 2] {
+			assert_ok!(Tictactoe::submit_transcript(
+				RuntimeOrigin::signed(player),
+				0,
+				transcript.clone().try_into().unwrap()
+			));
+		}
+
+		// Anyone can resolve the dispute with the transcript of both players.
+		assert_ok!(Tictactoe::resolve_dispute(
+			RuntimeOrigin::signed(5),
+			0,
+			transcript.try_into().unwrap()
+		));
+		System::assert_has_event((Event::DisputeResolved { game_index: 0, winner: 1 }).into());
+		System::assert_last_event(
+			(Event::GameEnded {
+				game_index: 0,
+				winner: 1,
+				jackpot: 2 * bet,
+				resolution: ResolutionKind::Dispute,
+			})
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&1), initial_balance + bet);
+		assert_eq!(Tictactoe::transcript_hashes(0), (None, None));
+	});
+}
+
+#[test]
+fn resolve_dispute_checks_transcript() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_disputed_game();
+		let transcript = vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)];
+		assert_ok!(Tictactoe::submit_transcript(
+			RuntimeOrigin::signed(1),
+			0,
+			transcript.clone().try_into().unwrap()
+		));
+
+		// Only the host submitted it.
+		assert_noop!(
+			Tictactoe::resolve_dispute(
+				RuntimeOrigin::signed(5),
+				0,
+				transcript.clone().try_into().unwrap()
+			),
+			Error::<Test>::TranscriptMismatch
+		);
+
+		// Joiner claims a different round.
+		let other = vec![(0, 0), (1, 0), (0, 1), (1, 1), (2, 2), (1, 2)];
+		assert_ok!(Tictactoe::submit_transcript(
+			RuntimeOrigin::signed(2),
+			0,
+			other.clone().try_into().unwrap()
+		));
+		for transcript in [transcript, other] {
+			assert_noop!(
+				Tictactoe::resolve_dispute(
+					RuntimeOrigin::signed(5),
+					0,
+					transcript.try_into().unwrap()
+				),
+				Error::<Test>::TranscriptMismatch
+			);
+		}
+	});
+}
+
+#[test]
+fn resolve_dispute_fails_with_undecided_transcript() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		create_disputed_game();
+		let transcript = vec![(0, 0), (1, 0), (0, 1)];
+		for player in [1, 2] {
+			assert_ok!(Tictactoe::submit_transcript(
+				RuntimeOrigin::signed(player),
+				0,
+				transcript.clone().try_into().unwrap()
+			));
+		}
+		assert_noop!(
+			Tictactoe::resolve_dispute(RuntimeOrigin::signed(5), 0, transcript.try_into().unwrap()),
+			Error::<Test>::InvalidTranscript
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_resolves_disputes() {
+	let mut ext = new_test_ext();
+	let (offchain, _) = TestOffchainExt::with_offchain_db(ext.offchain_db());
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	UintAuthorityId::set_all_keys(vec![5]);
+
+	let transcript = vec![(0, 0), (1, 0), (0, 1), (1, 1), (0, 2)];
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		create_disputed_game();
+		for player in [1, 2] {
+			assert_ok!(Tictactoe::submit_transcript(
+				RuntimeOrigin::signed(player),
+				0,
+				transcript.clone().try_into().unwrap()
+			));
+		}
+	});
+	// Transcripts indexed in the block are available to the offchain worker.
+	ext.persist_offchain_overlay();
+
+	ext.execute_with(|| {
+		Tictactoe::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, Some((0, ())));
+		assert_eq!(
+			tx.call,
+			RuntimeCall::Tictactoe(crate::Call::resolve_dispute {
+				game_index: 0,
+				transcript: transcript.clone().try_into().unwrap()
+			})
+		);
+
+		// The pending transaction isn't submitted again until the resend delay has passed.
+		Tictactoe::offchain_worker(2);
+		Tictactoe::offchain_worker(5);
+		assert!(pool_state.read().transactions.is_empty());
+		Tictactoe::offchain_worker(6);
+		assert_eq!(pool_state.write().transactions.drain(..).count(), 1);
+
+		assert_ok!(Tictactoe::resolve_dispute(
+			RuntimeOrigin::signed(5),
+			0,
+			transcript.clone().try_into().unwrap()
+		));
+	});
+	// The indexed transcript is cleared once the round is decided.
+	ext.persist_offchain_overlay();
+
+	ext.execute_with(|| {
+		let hash = <Test as frame_system::Config>::Hashing::hash_of(&transcript);
+		let indexed = StorageValueRef::persistent(&transcript_key(0, &hash));
+		assert_eq!(indexed.get::<Vec<(u8, u8)>>(), Ok(None));
+	});
+}
+
+#[test]
+fn claim_timeout_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let bet = 10;
+		let initial_balance = Balances::free_balance(&1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), bet, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		let claim = crate::Call::claim_timeout { game_index: 0 };
+
+		// No deadline until the round is played on chain.
+		assert_eq!(
+			Tictactoe::validate_unsigned(TransactionSource::External, &claim),
+			InvalidTransaction::Stale.into()
+		);
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(1), 0, (0, 0)));
+		assert_eq!(Tictactoe::move_deadlines(0), Some(11));
+
+		System::set_block_number(11);
+		assert_eq!(
+			Tictactoe::validate_unsigned(TransactionSource::External, &claim),
+			InvalidTransaction::Future.into()
+		);
+		assert_noop!(
+			Tictactoe::claim_timeout(RuntimeOrigin::none(), 0),
+			Error::<Test>::NotTimedOut
+		);
+
+		// Priority grows with the blocks past the deadline.
+		System::set_block_number(13);
+		let valid = Tictactoe::validate_unsigned(TransactionSource::External, &claim).unwrap();
+		assert_eq!(valid.priority, 102);
+		assert_eq!(valid.longevity, 10);
+		assert_eq!(valid.provides.len(), 1);
+
+		// Joiner missed the deadline, host takes the game.
+		assert_noop!(Tictactoe::claim_timeout(RuntimeOrigin::signed(3), 0), BadOrigin);
+		assert_ok!(Tictactoe::claim_timeout(RuntimeOrigin::none(), 0));
+		System::assert_has_event((Event::TimeoutClaimed { game_index: 0, winner: 1 }).into());
+		System::assert_last_event(
+			(Event::GameEnded {
+				game_index: 0,
+				winner: 1,
+				jackpot: 2 * bet,
+				resolution: ResolutionKind::Timeout,
+			})
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&1), initial_balance + bet);
+		assert_eq!(Tictactoe::move_deadlines(0), None);
+		assert_eq!(
+			Tictactoe::validate_unsigned(TransactionSource::External, &claim),
+			InvalidTransaction::Stale.into()
+		);
+	});
+}
+
+#[test]
+fn proposing_a_winner_stops_the_move_deadline() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(1), 0, (0, 0)));
+		assert_eq!(Tictactoe::move_deadlines(0), Some(11));
+
+		// The joiner is on turn but the host proposes a winner, the handshake takes over.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1));
+		assert_eq!(Tictactoe::move_deadlines(0), None);
+
+		// No later move can set a deadline again.
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(2), 0, (1, 1)),
+			Error::<Test>::WinnerProposed
+		);
+		System::set_block_number(20);
+		let claim = crate::Call::claim_timeout { game_index: 0 };
+		assert_eq!(
+			Tictactoe::validate_unsigned(TransactionSource::External, &claim),
+			InvalidTransaction::Stale.into()
+		);
+		assert_noop!(
+			Tictactoe::claim_timeout(RuntimeOrigin::none(), 0),
+			Error::<Test>::WinnerProposed
+		);
+
+		// Mediation is still reachable.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 2));
+		assert_eq!(Tictactoe::games(0).unwrap().status(), GameStatus::InMediation);
+	});
+}
+
+#[test]
+fn move_deadline_moves_with_the_turn() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(1), 0, (0, 0)));
+
+		System::set_block_number(8);
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(2), 0, (1, 1)));
+		assert_eq!(Tictactoe::move_deadlines(0), Some(18));
+
+		System::set_block_number(12);
+		assert_noop!(
+			Tictactoe::claim_timeout(RuntimeOrigin::none(), 0),
+			Error::<Test>::NotTimedOut
+		);
+	});
+}
+
+#[test]
+fn only_timeout_claims_are_unsigned() {
+	new_test_ext().execute_with(|| {
+		let call = crate::Call::join_game { game_index: 0 };
+		assert_eq!(
+			Tictactoe::validate_unsigned(TransactionSource::External, &call),
+			InvalidTransaction::Call.into()
+		);
+	});
+}
+
+/// Run `CheckTictactoeMove` before dispatching `call`, returns `None` if the fee was waived.
+fn charge_fee(who: u64, call: crate::Call<Test>) -> Option<()> {
+	let call = RuntimeCall::Tictactoe(call);
+	let info = call.get_dispatch_info();
+	CheckTictactoeMove::<Test, TestFee>::from(TestFee)
+		.pre_dispatch(&who, &call, &info, 0)
+		.unwrap()
+}
+
+#[test]
+fn players_move_without_fees() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		let play = |game_move| crate::Call::play_move { game_index: 0, game_move };
+		let host_balance = Balances::free_balance(&1);
+		let joiner_balance = Balances::free_balance(&2);
+
+		assert_eq!(charge_fee(1, play((0, 0))), None);
+		assert_eq!(Balances::free_balance(&1), host_balance);
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(1), 0, (0, 0)));
+
+		// A single fee-free call per game and block.
+		assert_eq!(charge_fee(2, play((1, 1))), Some(()));
+		assert_eq!(Balances::free_balance(&2), joiner_balance - 1);
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(2), 0, (1, 1)));
+
+		// Calls that would fail pay.
+		System::set_block_number(2);
+		assert_eq!(charge_fee(2, play((2, 2))), Some(()));
+		assert_eq!(charge_fee(1, play((1, 1))), Some(()));
+		assert_eq!(charge_fee(3, play((2, 2))), Some(()));
+		assert_eq!(
+			charge_fee(
+				1,
+				crate::Call::create_game {
+					bet: 10,
+					series_length: 1,
+					board_size: 3,
+					win_length: 3,
+					join_bonus: None
+				}
+			),
+			Some(())
+		);
+		assert_eq!(Balances::free_balance(&1), host_balance - 2);
+
+		assert_eq!(charge_fee(1, play((2, 2))), None);
+		assert_eq!(Tictactoe::feeless_calls(0), Some(2));
+	});
+}
+
+#[test]
+fn players_propose_winners_without_fees() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		let end_game = |winner| crate::Call::end_game { game_index: 0, winner };
+
+		// Proposing a non player pays.
+		assert_eq!(charge_fee(1, end_game(3)), Some(()));
+		assert_eq!(charge_fee(1, end_game(1)), None);
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1));
+
+		// Proposing twice pays.
+		System::set_block_number(2);
+		assert_eq!(charge_fee(1, end_game(1)), Some(()));
+
+		// Fee-free calls are valid in the pool without paying.
+		let call = RuntimeCall::Tictactoe(end_game(2));
+		let info = call.get_dispatch_info();
+		let check = CheckTictactoeMove::<Test, TestFee>::from(TestFee);
+		let feeless = check.validate(&2, &call, &info, 0).unwrap();
+		assert_eq!(feeless.priority, 0);
+		assert_eq!(feeless.provides, vec![("TictactoeFeeless", (0u32, 2u64)).encode()]);
+		assert_eq!(check.validate(&1, &call, &info, 0).unwrap().priority, 1);
+
+		// The record of fee-free calls is cleared when the game ends.
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		assert_eq!(charge_fee(2, end_game(1)), None);
+		assert_eq!(Tictactoe::feeless_calls(0), Some(2));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 1));
+		assert!(Tictactoe::games(0).unwrap().ended);
+		assert_eq!(Tictactoe::feeless_calls(0), None);
+	});
+}
+
+#[test]
+fn concurrent_fee_free_calls_of_both_players_are_kept() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		let check = CheckTictactoeMove::<Test, TestFee>::from(TestFee);
+		let end_game = RuntimeCall::Tictactoe(crate::Call::end_game { game_index: 0, winner: 1 });
+		let info = end_game.get_dispatch_info();
+
+		// Both players propose a winner in the same block, neither replaces the other in the pool.
+		let host_validity = check.validate(&1, &end_game, &info, 0).unwrap();
+		let joiner_validity = check.validate(&2, &end_game, &info, 0).unwrap();
+		assert_ne!(host_validity.provides, joiner_validity.provides);
+
+		// The first one included is free, the second one pays and still goes through.
+		let joiner_balance = Balances::free_balance(&2);
+		assert_eq!(charge_fee(1, crate::Call::end_game { game_index: 0, winner: 1 }), None);
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1));
+		assert_eq!(charge_fee(2, crate::Call::end_game { game_index: 0, winner: 1 }), Some(()));
+		assert_eq!(Balances::free_balance(&2), joiner_balance - 1);
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 1));
+		assert!(Tictactoe::games(0).unwrap().ended);
+	});
+}
+
+#[test]
+fn set_session_key_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Tictactoe::set_session_key(RuntimeOrigin::signed(1), 11, Some(0)));
+		System::assert_last_event(
+			(Event::SessionKeySet { player: 1, session_key: 11, game_index: Some(0) }).into(),
+		);
+		assert_eq!(Tictactoe::session_keys(1, 11), Some(Some(0)));
+
+		// Registering again changes the game.
+		assert_ok!(Tictactoe::set_session_key(RuntimeOrigin::signed(1), 11, None));
+		assert_eq!(Tictactoe::session_keys(1, 11), Some(None));
+
+		// Another player registering the same key doesn't lock it for the first one.
+		assert_ok!(Tictactoe::set_session_key(RuntimeOrigin::signed(2), 11, Some(3)));
+		assert_eq!(Tictactoe::session_keys(1, 11), Some(None));
+		assert_eq!(Tictactoe::session_keys(2, 11), Some(Some(3)));
+		assert_noop!(
+			Tictactoe::set_session_key(RuntimeOrigin::signed(1), 1, None),
+			Error::<Test>::InvalidSessionKey
+		);
+	});
+}
+
+#[test]
+fn remove_session_key_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(Tictactoe::set_session_key(RuntimeOrigin::signed(1), 11, None));
+		assert_noop!(
+			Tictactoe::remove_session_key(RuntimeOrigin::signed(2), 11),
+			Error::<Test>::NotTheSessionKeyOwner
+		);
+		assert_ok!(Tictactoe::remove_session_key(RuntimeOrigin::signed(1), 11));
+		System::assert_last_event((Event::SessionKeyRemoved { player: 1, session_key: 11 }).into());
+		assert_eq!(Tictactoe::session_keys(1, 11), None);
+		assert_noop!(
+			Tictactoe::remove_session_key(RuntimeOrigin::signed(1), 11),
+			Error::<Test>::NotTheSessionKeyOwner
+		);
+	});
+}
+
+#[test]
+fn session_keys_play_for_their_player() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		let bet = 10;
+		let initial_balance = Balances::free_balance(&1);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), bet, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Tictactoe::set_session_key(RuntimeOrigin::signed(1), 11, Some(0)));
+		assert_ok!(Tictactoe::set_session_key(RuntimeOrigin::signed(2), 12, None));
+
+		// Session keys move as their players, without funds or fees.
+		assert_eq!(
+			charge_fee(11, crate::Call::play_move { game_index: 0, game_move: (0, 0) }),
+			None
+		);
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(11), 0, (0, 0)));
+		System::assert_last_event(
+			(Event::MovePlayed { game_index: 0, player: 1, game_move: (0, 0) }).into(),
+		);
+		assert_noop!(
+			Tictactoe::play_move(RuntimeOrigin::signed(11), 0, (1, 1)),
+			Error::<Test>::NotYourTurn
+		);
+		assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(12), 0, (1, 1)));
+		System::assert_last_event(
+			(Event::MovePlayed { game_index: 0, player: 2, game_move: (1, 1) }).into(),
+		);
+		for (session_key, game_move) in [(11, (0, 1)), (12, (2, 2)), (11, (0, 2))] {
+			assert_ok!(Tictactoe::play_move(RuntimeOrigin::signed(session_key), 0, game_move));
+		}
+
+		// Settlement pays the player account.
+		System::assert_last_event(
+			(Event::GameEnded {
+				game_index: 0,
+				winner: 1,
+				jackpot: 2 * bet,
+				resolution: ResolutionKind::Board,
+			})
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&1), initial_balance + bet);
+		assert_eq!(Balances::free_balance(&11), 0);
+	});
+}
+
+#[test]
+fn session_keys_are_scoped_to_their_game() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		let bet = 10;
+		let initial_balance = Balances::free_balance(&2);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), bet, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), bet, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 1));
+		assert_ok!(Tictactoe::set_session_key(RuntimeOrigin::signed(1), 11, Some(1)));
+
+		// A key registered for another game or by a non player acts as itself.
+		assert_noop!(
+			Tictactoe::end_game(RuntimeOrigin::signed(11), 0, 2),
+			Error::<Test>::NotAPlayer
+		);
+		assert_ok!(Tictactoe::set_session_key(RuntimeOrigin::signed(3), 13, None));
+		assert_noop!(
+			Tictactoe::end_game(RuntimeOrigin::signed(13), 1, 2),
+			Error::<Test>::NotAPlayer
+		);
+
+		// A key registered by both players acts for neither of them.
+		assert_ok!(Tictactoe::set_session_key(RuntimeOrigin::signed(2), 11, None));
+		assert_noop!(
+			Tictactoe::end_game(RuntimeOrigin::signed(11), 1, 2),
+			Error::<Test>::NotAPlayer
+		);
+		assert_ok!(Tictactoe::remove_session_key(RuntimeOrigin::signed(2), 11));
+
+		// Host agrees through its session key, the winner is paid.
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 1, 2));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(11), 1, 2));
+		System::assert_last_event(
+			(Event::GameEnded {
+				game_index: 1,
+				winner: 2,
+				jackpot: 2 * bet,
+				resolution: ResolutionKind::Agreement,
+			})
+			.into(),
+		);
+		assert_eq!(Balances::free_balance(&2), initial_balance);
+		assert_eq!(Balances::free_balance(&11), 0);
+	});
+}
+
+#[test]
+fn game_events_are_indexed_by_game() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		let bet = 10;
+		let safeguard_deposit = 1;
+		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), bet, 1, 3, 3, None));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(3), bet, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 1));
+
+		// Both deposits are returned before the game ends.
+		let game_events: Vec<_> = System::event_topics(&Tictactoe::game_topic(0))
+			.into_iter()
+			.map(|(_, index)| System::events()[index as usize].event.clone())
+			.collect();
+		assert_eq!(
+			game_events,
+			vec![
+				Event::GameCreated {
+					game_index: 0,
+					host: 1,
+					bet,
+					series_length: 1,
+					board_size: 3,
+					win_length: 3,
+					join_bonus: 0
+				},
+				Event::PlayerJoined { game_index: 0, player: 2, bet, join_bonus: 0 },
+				Event::WinnerProposed { game_index: 0, winner: 1, proposer: 1 },
+				Event::DepositReturned { game_index: 0, player: 1, amount: safeguard_deposit },
+				Event::DepositReturned { game_index: 0, player: 2, amount: safeguard_deposit },
+				Event::GameEnded {
+					game_index: 0,
+					winner: 1,
+					jackpot: 2 * bet,
+					resolution: ResolutionKind::Agreement
+				},
+			]
+			.into_iter()
+			.map(RuntimeEvent::from)
+			.collect::<Vec<_>>()
+		);
+		assert_eq!(System::event_topics(&Tictactoe::game_topic(1)).len(), 1);
+		assert!(System::event_topics(&Tictactoe::game_topic(2)).is_empty());
+	});
+}
+
+#[test]
+fn storage_deposit_is_refunded_on_prune() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		GameDepositPerByte::set(1);
+		let storage_deposit = Tictactoe::game_storage_deposit();
+		assert!(storage_deposit > 0);
+		let bet = 10;
+		let initial_balance = Balances::free_balance(&1);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), bet, 1, 3, 3, None));
+		assert_eq!(Balances::free_balance(&1), initial_balance - bet - storage_deposit);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 20 + bet + storage_deposit);
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+
+		// Only ended games can be pruned.
+		assert_noop!(
+			Tictactoe::prune_game(RuntimeOrigin::signed(3), 0),
+			Error::<Test>::GameNotEnded
+		);
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(1), 0, 1));
+		assert_ok!(Tictactoe::end_game(RuntimeOrigin::signed(2), 0, 1));
+		assert_eq!(Balances::free_balance(&1), initial_balance + bet - storage_deposit);
+
+		// Anyone can prune, the host gets the storage deposit back.
+		assert_ok!(Tictactoe::prune_game(RuntimeOrigin::signed(3), 0));
+		System::assert_last_event((Event::GamePruned { game_index: 0, storage_deposit }).into());
+		assert_eq!(Tictactoe::games(0), None);
+		assert_eq!(Balances::free_balance(&1), initial_balance + bet);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 20);
+		assert_noop!(
+			Tictactoe::prune_game(RuntimeOrigin::signed(3), 0),
+			Error::<Test>::GameDoesNotExist
+		);
+	});
+}
+
+#[test]
+fn open_games_are_capped() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		MaxOpenGames::set(2);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(3), 10, 1, 3, 3, None));
+		assert_eq!(Tictactoe::open_games(), 2);
+		assert_noop!(
+			Tictactoe::create_game(RuntimeOrigin::signed(4), 10, 1, 3, 3, None),
+			Error::<Test>::TooManyOpenGames
+		);
+
+		// Ending a game makes room for a new one, pruning is not needed.
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Tictactoe::force_end_game(RuntimeOrigin::root(), 0, 1, 1));
+		assert_eq!(Tictactoe::open_games(), 1);
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(4), 10, 1, 3, 3, None));
+		assert_eq!(Tictactoe::open_games(), 2);
+	});
+}
+
+#[test]
+fn force_end_games_reports_each_entry() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		let bet = 10;
+		let safeguard_deposit = 1;
+		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
+		for (host, joiner) in [(1, 2), (3, 4)] {
+			assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(host), bet, 1, 3, 3, None));
+			let game_index = Tictactoe::game_index() - 1;
+			assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(joiner), game_index));
+		}
+		let initial_balance = Balances::free_balance(&1);
+		let settlements: BoundedVec<_, ConstU32<4>> =
+			vec![(0, 1, 1), (7, 1, 1), (1, 4, 4), (0, 2, 2)].try_into().unwrap();
+		assert_noop!(
+			Tictactoe::force_end_games(RuntimeOrigin::signed(1), settlements.clone()),
+			BadOrigin
+		);
+
+		// Failing entries are reported and don't revert the rest of the batch.
+		assert_ok!(Tictactoe::force_end_games(RuntimeOrigin::root(), settlements));
+		System::assert_has_event((Event::ForceEndSucceeded { game_index: 0 }).into());
+		System::assert_has_event(
+			(Event::ForceEndFailed {
+				game_index: 7,
+				error: Error::<Test>::GameDoesNotExist.into(),
+			})
+			.into(),
+		);
+		System::assert_has_event((Event::ForceEndSucceeded { game_index: 1 }).into());
+		System::assert_last_event(
+			(Event::ForceEndFailed {
+				game_index: 0,
+				error: Error::<Test>::GameAlreadyEnded.into(),
+			})
+			.into(),
+		);
+		assert!(Tictactoe::games(0).unwrap().ended);
+		assert!(Tictactoe::games(1).unwrap().ended);
+		assert_eq!(Tictactoe::games(0).unwrap().handshake, (Some(1), Some(1)));
+		assert_eq!(Balances::free_balance(&1), initial_balance + 2 * bet + safeguard_deposit);
+		assert_eq!(Tictactoe::open_games(), 0);
+	});
+}
+
+#[test]
+fn rematch_works() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		let bet = 10;
+		let safeguard_deposit = 1;
+		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), bet, 3, 4, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_noop!(
+			Tictactoe::offer_rematch(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::GameNotEnded
+		);
+		assert_ok!(Tictactoe::force_end_game(RuntimeOrigin::root(), 0, 1, 1));
+
+		assert_noop!(
+			Tictactoe::offer_rematch(RuntimeOrigin::signed(3), 0),
+			Error::<Test>::NotAPlayer
+		);
+		assert_ok!(Tictactoe::offer_rematch(RuntimeOrigin::signed(1), 0));
+		System::assert_last_event(
+			(Event::RematchOffered { game_index: 0, player: 1, expires_at: 6 }).into(),
+		);
+		assert_noop!(
+			Tictactoe::offer_rematch(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::RematchAlreadyOffered
+		);
+		assert_noop!(
+			Tictactoe::accept_rematch(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::OwnRematchOffer
+		);
+		assert_noop!(
+			Tictactoe::accept_rematch(RuntimeOrigin::signed(3), 0),
+			Error::<Test>::NotAPlayer
+		);
+
+		// Stakes of both players are escrowed in one step.
+		let host_balance = Balances::free_balance(&1);
+		let joiner_balance = Balances::free_balance(&2);
+		assert_ok!(Tictactoe::accept_rematch(RuntimeOrigin::signed(2), 0));
+		System::assert_has_event(
+			(Event::RematchAccepted { game_index: 0, rematch_index: 1 }).into(),
+		);
+		System::assert_last_event(
+			(Event::PlayerJoined { game_index: 1, player: 1, bet, join_bonus: 0 }).into(),
+		);
+		assert_eq!(Balances::free_balance(&1), host_balance - bet - safeguard_deposit);
+		assert_eq!(Balances::free_balance(&2), joiner_balance - bet - safeguard_deposit);
+		assert_eq!(Tictactoe::rematch_offers(0), None);
+
+		// Same stake and settings, the joiner of the ended game moves first.
+		let rematch = Tictactoe::games(1).unwrap();
+		assert_eq!(rematch.payout_addresses, (Some(2), Some(1)));
+		assert_eq!(rematch.first_mover(), Some(2));
+		assert_eq!((rematch.bet, rematch.series_length), (bet, 3));
+		assert_eq!((rematch.board_size, rematch.win_length), (4, 3));
+		assert_eq!((rematch.ended, rematch.round_wins), (false, (0, 0)));
+		assert_eq!(Tictactoe::open_games(), 1);
+	});
+}
+
+#[test]
+fn rematch_offers_expire() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(1), 10, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		assert_ok!(Tictactoe::force_end_game(RuntimeOrigin::root(), 0, 1, 1));
+		assert_noop!(
+			Tictactoe::accept_rematch(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::NoRematchOffer
+		);
+		assert_ok!(Tictactoe::offer_rematch(RuntimeOrigin::signed(1), 0));
+
+		System::set_block_number(7);
+		assert_noop!(
+			Tictactoe::accept_rematch(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::RematchOfferExpired
+		);
+
+		// Once expired, a new offer can be made.
+		assert_ok!(Tictactoe::offer_rematch(RuntimeOrigin::signed(2), 0));
+		assert_eq!(Tictactoe::rematch_offers(0), Some((2, 12)));
+		assert_ok!(Tictactoe::accept_rematch(RuntimeOrigin::signed(1), 0));
+		assert_eq!(Tictactoe::games(1).unwrap().payout_addresses, (Some(2), Some(1)));
+	});
+}
+
+#[test]
+fn house_games_cant_be_rematched() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		let house = Tictactoe::house_pot_account();
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), house, 50));
+		assert_ok!(Tictactoe::create_vs_house(RuntimeOrigin::signed(1), 10, 0));
+		assert_ok!(Tictactoe::force_end_game(RuntimeOrigin::root(), 0, house, house));
+		assert_noop!(
+			Tictactoe::offer_rematch(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::RematchNotAllowed
+		);
+	});
+}
+
+#[test]
+fn join_bonus_is_paid_to_joiner() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (bet, join_bonus, safeguard_deposit) = (10, 5, 1);
+		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
+		assert_ok!(Tictactoe::create_game(
+			RuntimeOrigin::signed(1),
+			bet,
+			1,
+			3,
+			3,
+			Some(join_bonus)
+		));
+		assert_eq!(Tictactoe::games(0).unwrap().join_bonus, join_bonus);
+		assert_eq!(Balances::free_balance(&1), 100 - bet - safeguard_deposit - join_bonus);
+
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(2), 0));
+		System::assert_last_event(
+			(Event::PlayerJoined { game_index: 0, player: 2, bet, join_bonus }).into(),
+		);
+		assert_eq!(Balances::free_balance(&2), 100 - bet - safeguard_deposit + join_bonus);
+		// Pallet only keeps the bets and safeguard deposits.
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 2 * (bet + safeguard_deposit));
+	});
+}
+
+#[test]
+fn cancel_game_refunds_bonus() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		let (bet, join_bonus, safeguard_deposit) = (10, 5, 1);
+		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
+		assert_ok!(Tictactoe::create_game(
+			RuntimeOrigin::signed(1),
+			bet,
+			1,
+			3,
+			3,
+			Some(join_bonus)
+		));
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(3), bet, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(4), 1));
+
+		// Only the host can cancel, and only before someone joins.
+		assert_noop!(
+			Tictactoe::cancel_game(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::NotAPlayer
+		);
+		assert_noop!(Tictactoe::cancel_game(RuntimeOrigin::signed(3), 1), Error::<Test>::GameFull);
+
+		assert_ok!(Tictactoe::cancel_game(RuntimeOrigin::signed(1), 0));
+		let refund = bet + safeguard_deposit + join_bonus;
+		System::assert_last_event((Event::GameCancelled { game_index: 0, refund }).into());
+		assert_eq!(Balances::free_balance(&1), 100);
+		assert!(Tictactoe::games(0).unwrap().ended);
+		assert_eq!(Tictactoe::open_games(), 1);
+
+		assert_noop!(
+			Tictactoe::join_game(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::GameAlreadyEnded
+		);
+		assert_noop!(
+			Tictactoe::cancel_game(RuntimeOrigin::signed(1), 0),
+			Error::<Test>::GameAlreadyEnded
+		);
+	});
+}
+
+#[test]
+fn open_games_expire() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		// Fund pallet account
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 20));
+		let (bet, join_bonus, safeguard_deposit) = (10, 5, 1);
+		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
+		assert_ok!(Tictactoe::create_game(
+			RuntimeOrigin::signed(1),
+			bet,
+			1,
+			3,
+			3,
+			Some(join_bonus)
+		));
+		assert_eq!(Tictactoe::move_deadlines(0), Some(21));
+
+		// Joining removes the deadline.
+		assert_ok!(Tictactoe::create_game(RuntimeOrigin::signed(3), bet, 1, 3, 3, None));
+		assert_ok!(Tictactoe::join_game(RuntimeOrigin::signed(4), 1));
+		assert_eq!(Tictactoe::move_deadlines(1), None);
+
+		let claim = crate::Call::claim_timeout { game_index: 0 };
+		System::set_block_number(21);
+		assert_eq!(
+			Tictactoe::validate_unsigned(TransactionSource::External, &claim),
+			InvalidTransaction::Future.into()
+		);
+		assert_noop!(
+			Tictactoe::claim_timeout(RuntimeOrigin::none(), 0),
+			Error::<Test>::NotTimedOut
+		);
+
+		// Nobody joined in time, the game can't be joined anymore and the host is refunded.
+		System::set_block_number(22);
+		assert_noop!(
+			Tictactoe::join_game(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::JoinDeadlinePassed
+		);
+		assert!(Tictactoe::validate_unsigned(TransactionSource::External, &claim).is_ok());
+		assert_ok!(Tictactoe::claim_timeout(RuntimeOrigin::none(), 0));
+		let refund = bet + safeguard_deposit + join_bonus;
+		System::assert_last_event((Event::GameExpired { game_index: 0, refund }).into());
+		assert_eq!(Balances::free_balance(&1), 100);
+		assert!(Tictactoe::games(0).unwrap().ended);
+		assert_eq!(Tictactoe::move_deadlines(0), None);
+		assert_eq!(Tictactoe::open_games(), 1);
+		assert_eq!(
+			Tictactoe::validate_unsigned(TransactionSource::External, &claim),
+			InvalidTransaction::Stale.into()
+		);
+		assert_noop!(
+			Tictactoe::join_game(RuntimeOrigin::signed(2), 0),
+			Error::<Test>::GameAlreadyEnded
+		);
+	});
+}
+
+#[test]
+fn force_end_game_rejects_unjoined_games() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let (bet, join_bonus, safeguard_deposit) = (10, 5, 1);
+		assert_ok!(Tictactoe::set_safeguard_deposit(RuntimeOrigin::root(), safeguard_deposit));
+		assert_ok!(Tictactoe::create_game(
+			RuntimeOrigin::signed(1),
+			bet,
+			1,
+			3,
+			3,
+			Some(join_bonus)
+		));
+		let stake = bet + safeguard_deposit + join_bonus;
+		assert_eq!(Balances::free_balance(&1), 100 - stake);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), stake);
+
+		// Nothing is paid out of the pallet account.
+		assert_noop!(
+			Tictactoe::force_end_game(RuntimeOrigin::root(), 0, 1, 1),
+			Error::<Test>::GameNotJoined
+		);
+		assert_eq!(Balances::free_balance(&1), 100 - stake);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), stake);
+
+		// The host still gets the whole stake back by cancelling.
+		assert_ok!(Balances::transfer(RuntimeOrigin::signed(5), Tictactoe::account_id(), 1));
+		assert_ok!(Tictactoe::cancel_game(RuntimeOrigin::signed(1), 0));
+		assert_eq!(Balances::free_balance(&1), 100);
+		assert_eq!(Balances::free_balance(Tictactoe::account_id()), 1);
+	});
+}
@@ -0,0 +1,78 @@
+//! Move timeouts and open game expiry.
+//!
+//! Once a round is being played on chain, the player on turn has `MoveTimeout` blocks to move. If
+//! the deadline passes, anyone can claim the timeout and the opponent takes the round. Open games
+//! have `OpenGameTimeout` blocks to be joined, claiming the timeout of a game nobody joined
+//! expires it and refunds the host. The outcome only depends on chain state, so claims are
+//! submitted as unsigned transactions and checked in `validate_unsigned` before reaching a block,
+//! without fees for the claimant.
+//!
+//! Once a player proposes a winner with `end_game`, the deadline is removed and the round follows
+//! the handshake, mediation and dispute flow instead.
+
+use super::*;
+use frame_support::pallet_prelude::*;
+use sp_runtime::traits::UniqueSaturatedInto;
+
+impl<T: Config> Pallet<T> {
+	/// Give the player on turn `MoveTimeout` blocks from now to move.
+	pub(crate) fn set_move_deadline(game_index: u32) {
+		let now = frame_system::Pallet::<T>::block_number();
+		MoveDeadlines::<T>::insert(game_index, now.saturating_add(T::MoveTimeout::get()));
+	}
+
+	/// Give an open game `OpenGameTimeout` blocks from now to be joined.
+	pub(crate) fn set_join_deadline(game_index: u32) {
+		let now = frame_system::Pallet::<T>::block_number();
+		MoveDeadlines::<T>::insert(game_index, now.saturating_add(T::OpenGameTimeout::get()));
+	}
+
+	/// Returns true if the game has a move deadline and it has passed.
+	pub(crate) fn move_deadline_passed(game_index: u32) -> bool {
+		let now = frame_system::Pallet::<T>::block_number();
+		Self::move_deadlines(game_index).map_or(false, |deadline| now > deadline)
+	}
+
+	/// End a game nobody joined before its deadline, refunding the bet, safeguard deposit and join
+	/// bonus to the host. The storage deposit is refunded when the game is pruned.
+	pub(crate) fn expire_game(
+		game_index: u32,
+		mut game: Game<BalanceOf<T>, T::AccountId>,
+		host: T::AccountId,
+	) -> DispatchResult {
+		game.ended = true;
+		let refund = game
+			.bet
+			.saturating_add(Self::safeguard_deposit())
+			.saturating_add(game.join_bonus);
+		Games::<T>::insert(game_index, game);
+		MoveDeadlines::<T>::remove(game_index);
+		Self::close_game();
+		Self::transfer_from_pallet(host, refund)?;
+
+		Self::deposit_game_event(game_index, Event::GameExpired { game_index, refund });
+		Ok(())
+	}
+
+	/// Validate an unsigned timeout claim.
+	/// Claims get a higher priority the longer the deadline has been missed, only one claim per
+	/// game is kept in the pool.
+	pub(crate) fn validate_timeout_claim(game_index: u32) -> TransactionValidity {
+		ensure!(
+			Self::games(game_index)
+				.map_or(false, |game| !game.ended && game.handshake == (None, None)),
+			InvalidTransaction::Stale
+		);
+		let deadline = Self::move_deadlines(game_index).ok_or(InvalidTransaction::Stale)?;
+		let now = frame_system::Pallet::<T>::block_number();
+		ensure!(now > deadline, InvalidTransaction::Future);
+
+		let overdue: u64 = now.saturating_sub(deadline).unique_saturated_into();
+		ValidTransaction::with_tag_prefix("TictactoeTimeout")
+			.priority(T::UnsignedPriority::get().saturating_add(overdue))
+			.and_provides(game_index)
+			.longevity(T::MoveTimeout::get().unique_saturated_into())
+			.propagate(true)
+			.build()
+	}
+}
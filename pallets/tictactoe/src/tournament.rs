@@ -0,0 +1,249 @@
+//! Single-elimination tournaments.
+//!
+//! An organizer opens a tournament with an entry fee, a bracket size and a prize distribution.
+//! Once the bracket is full, the first round is created as regular [`Game`]s that players end
+//! with `end_game` as usual. Whenever all games of a round are decided, their winners are paired
+//! into the next round, and when the final ends the prize pool is paid out following the stored
+//! distribution.
+//!
+//! Bracket games get a move deadline as soon as they are created, so a pair that neither plays
+//! nor settles its game can be timed out with `claim_timeout` and the bracket goes on.
+//!
+//! A bracket of `n` players plays `n - 1` games. They all count as open games from the moment the
+//! bracket fills, so later rounds never hit `MaxOpenGames` and ending a bracket game can't fail
+//! because other players opened games in the meantime.
+
+use super::*;
+use frame_support::{
+	pallet_prelude::*,
+	sp_runtime::{PerThing, Permill},
+	sp_std::prelude::*,
+	CloneNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+
+/// Lifecycle of a tournament.
+#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum TournamentStatus {
+	/// Players can register until the bracket is full.
+	Registering,
+	/// Bracket games are being played.
+	InProgress,
+	/// The final has ended and the prize pool has been paid out.
+	Finished,
+	/// The organizer cancelled the tournament and entry fees were refunded.
+	Cancelled,
+}
+
+/// Tournament struct
+#[derive(
+	CloneNoBound, Encode, Decode, PartialEqNoBound, RuntimeDebugNoBound, TypeInfo, MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct Tournament<T: Config> {
+	// Account that created the tournament. Receives the organizer cut.
+	pub(crate) organizer: T::AccountId,
+	// Amount each player pays to register. Prize pool is the sum of all entry fees.
+	pub(crate) entry_fee: BalanceOf<T>,
+	// Bracket size, the tournament starts as soon as it is reached.
+	pub(crate) max_players: u32,
+	// Share of the prize pool for each final standing, champion first.
+	pub(crate) prize_distribution: BoundedVec<Permill, T::MaxPrizePositions>,
+	// Share of the prize pool taken by the organizer before prizes are paid.
+	pub(crate) organizer_cut: Permill,
+	// Registered players in registration order, used to seed the first round.
+	pub(crate) players: BoundedVec<T::AccountId, T::MaxTournamentPlayers>,
+	// Current round, 0 while registering.
+	pub(crate) round: u32,
+	// Games of the current round in bracket order.
+	pub(crate) round_games: BoundedVec<u32, T::MaxTournamentPlayers>,
+	// Winner of each game of the current round once it is decided.
+	pub(crate) advancing: BoundedVec<Option<T::AccountId>, T::MaxTournamentPlayers>,
+	// Eliminated players in elimination order.
+	pub(crate) eliminated: BoundedVec<T::AccountId, T::MaxTournamentPlayers>,
+	// Indicates the stage of the tournament.
+	pub(crate) status: TournamentStatus,
+}
+
+impl<T: Config> Tournament<T> {
+	/// Returns the prize pool held by the pallet account until it is paid out or refunded.
+	pub fn held_funds(&self) -> BalanceOf<T> {
+		match self.status {
+			TournamentStatus::Registering | TournamentStatus::InProgress =>
+				self.entry_fee.saturating_mul((self.players.len() as u32).into()),
+			TournamentStatus::Finished | TournamentStatus::Cancelled => Zero::zero(),
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Check that the bracket size is a power of two within bounds and that the prize
+	/// distribution doesn't hand out more than the whole pool.
+	pub(crate) fn validate_tournament(
+		max_players: u32,
+		prize_distribution: &[Permill],
+	) -> DispatchResult {
+		ensure!(
+			max_players >= 2 &&
+				max_players.is_power_of_two() &&
+				max_players <= T::MaxTournamentPlayers::get(),
+			Error::<T>::InvalidTournamentSize
+		);
+		ensure!(
+			!prize_distribution.is_empty() && prize_distribution.len() as u32 <= max_players,
+			Error::<T>::InvalidPrizeDistribution
+		);
+		let total: u64 = prize_distribution.iter().map(|share| share.deconstruct() as u64).sum();
+		ensure!(total <= Permill::one().deconstruct() as u64, Error::<T>::InvalidPrizeDistribution);
+		Ok(())
+	}
+
+	/// Pair players two by two into new bracket games and make them the current round.
+	pub(crate) fn start_round(
+		tournament_index: u32,
+		tournament: &mut Tournament<T>,
+		players: Vec<T::AccountId>,
+	) -> DispatchResult {
+		let mut round_games = Vec::with_capacity(players.len() / 2);
+		for pair in players.chunks_exact(2) {
+			let game_index =
+				Self::create_tournament_game(tournament_index, pair[0].clone(), pair[1].clone())?;
+			round_games.push(game_index);
+		}
+
+		let advancing: Vec<Option<T::AccountId>> = round_games.iter().map(|_| None).collect();
+		tournament.advancing =
+			BoundedVec::try_from(advancing).map_err(|_| Error::<T>::TournamentFull)?;
+		tournament.round_games =
+			BoundedVec::try_from(round_games).map_err(|_| Error::<T>::TournamentFull)?;
+		tournament.round = tournament.round.saturating_add(1);
+		tournament.status = TournamentStatus::InProgress;
+
+		Self::deposit_event(Event::TournamentRoundStarted {
+			tournament_index,
+			round: tournament.round,
+		});
+		Ok(())
+	}
+
+	/// Record the result of a bracket game. Once the round is decided, winners are paired into
+	/// the next round or, after the final, the prize pool is paid out.
+	pub(crate) fn advance_tournament(
+		tournament_index: u32,
+		game_index: u32,
+		winner: T::AccountId,
+		loser: T::AccountId,
+	) -> DispatchResult {
+		Tournaments::<T>::try_mutate(tournament_index, |tournament| -> DispatchResult {
+			let tournament =
+				tournament.as_mut().ok_or_else(|| Error::<T>::TournamentDoesNotExist)?;
+			let slot = tournament
+				.round_games
+				.iter()
+				.position(|index| *index == game_index)
+				.ok_or_else(|| Error::<T>::GameDoesNotExist)?;
+			if let Some(advancing) = tournament.advancing.get_mut(slot) {
+				*advancing = Some(winner);
+			}
+			tournament.eliminated.try_push(loser).map_err(|_| Error::<T>::TournamentFull)?;
+
+			// Wait for the remaining games of the round.
+			if tournament.advancing.iter().any(|advancing| advancing.is_none()) {
+				return Ok(())
+			}
+
+			let mut winners: Vec<T::AccountId> =
+				tournament.advancing.iter().flatten().cloned().collect();
+			if winners.len() > 1 {
+				Self::start_round(tournament_index, tournament, winners)
+			} else {
+				let champion = winners.pop().ok_or_else(|| Error::<T>::BadAddress)?;
+				Self::pay_prizes(tournament_index, tournament, champion)
+			}
+		})
+	}
+
+	/// Give every registered player its entry fee back.
+	pub(crate) fn refund_entry_fees(tournament: &Tournament<T>) -> DispatchResult {
+		for player in tournament.players.iter() {
+			Self::transfer_from_pallet(player.clone(), tournament.entry_fee)?;
+		}
+		Ok(())
+	}
+
+	/// Create a bracket game between two registered players.
+	/// Stakes were already collected as entry fees, so the game holds no bet nor safeguard
+	/// deposit. Bracket games are played on a classic 3x3 board and the first mover has
+	/// `MoveTimeout` blocks to open it. Its open game slot was reserved when the bracket filled.
+	fn create_tournament_game(
+		tournament_index: u32,
+		host: T::AccountId,
+		joiner: T::AccountId,
+	) -> Result<u32, DispatchError> {
+		let game_index = Self::game_index();
+		let new_game_index = game_index.checked_add(1).ok_or_else(|| Error::<T>::IndexOverflow)?;
+		let game = Game {
+			bet: Zero::zero(),
+			payout_addresses: (Some(host.clone()), Some(joiner)),
+			ended: false,
+			handshake: (None, None),
+			tournament: Some(tournament_index),
+			series_length: 1,
+			round_wins: (0, 0),
+			board_size: 3,
+			win_length: 3,
+			house_difficulty: None,
+			storage_deposit: Zero::zero(),
+			join_bonus: Zero::zero(),
+		};
+		Games::<T>::insert(game_index, game);
+		GameIndex::<T>::put(new_game_index);
+		Self::set_move_deadline(game_index);
+		Self::deposit_game_event(
+			game_index,
+			Event::GameCreated {
+				game_index,
+				host,
+				bet: Zero::zero(),
+				series_length: 1,
+				board_size: 3,
+				win_length: 3,
+				join_bonus: Zero::zero(),
+			},
+		);
+		Ok(game_index)
+	}
+
+	/// Pay out the prize pool of a finished tournament.
+	/// Standings go from the champion down to the first eliminated player, so players
+	/// eliminated in later rounds rank higher. The organizer cut is taken first and whatever the
+	/// distribution leaves unassigned, rounding included, goes to the champion.
+	fn pay_prizes(
+		tournament_index: u32,
+		tournament: &mut Tournament<T>,
+		champion: T::AccountId,
+	) -> DispatchResult {
+		let prize_pool =
+			tournament.entry_fee.saturating_mul((tournament.players.len() as u32).into());
+		let organizer_cut = tournament.organizer_cut * prize_pool;
+		let prizes = prize_pool.saturating_sub(organizer_cut);
+
+		let mut champion_prize = prizes;
+		for (share, player) in tournament
+			.prize_distribution
+			.iter()
+			.skip(1)
+			.zip(tournament.eliminated.iter().rev())
+		{
+			let prize = *share * prizes;
+			champion_prize = champion_prize.saturating_sub(prize);
+			Self::transfer_from_pallet(player.clone(), prize)?;
+		}
+		Self::transfer_from_pallet(champion.clone(), champion_prize)?;
+		Self::transfer_from_pallet(tournament.organizer.clone(), organizer_cut)?;
+
+		tournament.status = TournamentStatus::Finished;
+		Self::deposit_event(Event::TournamentEnded { tournament_index, champion, prize_pool });
+		Ok(())
+	}
+}
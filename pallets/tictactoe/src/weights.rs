@@ -20,6 +20,11 @@
 // --wasm-execution=compiled
 // --template=./.maintain/frame-weight-template.hbs
 // --output=./pallets/tictactoe/src/weights.rs
+//
+// Only `set_safeguard_deposit` and `withdraw_funds` are unchanged since the run above. The other
+// functions were not measured: their storage proofs follow the `MaxEncodedLen` of the runtime
+// types and their times are estimates. Rerun the command above on reference hardware and commit
+// its output before relying on them.
 
 #![cfg_attr(rustfmt, rustfmt_skip)]
 #![allow(unused_parens)]
@@ -33,10 +38,35 @@ use core::marker::PhantomData;
 pub trait WeightInfo {
 	fn create_game() -> Weight;
 	fn join_game() -> Weight;
-	fn end_game() -> Weight;
+	fn end_game_first_proposal() -> Weight;
+	fn end_game_mismatch() -> Weight;
+	fn end_game_agreement() -> Weight;
 	fn set_safeguard_deposit() -> Weight;
-	fn force_end_game() -> Weight;
+	fn force_end_game(n: u32, ) -> Weight;
+	fn force_end_tournament_game(p: u32, ) -> Weight;
 	fn withdraw_funds() -> Weight;
+	fn create_tournament() -> Weight;
+	fn register_for_tournament(p: u32, ) -> Weight;
+	fn cancel_tournament(p: u32, ) -> Weight;
+	fn settle_tournament_game(p: u32, ) -> Weight;
+	fn place_side_bet() -> Weight;
+	fn settle_side_bets(n: u32, ) -> Weight;
+	fn play_move(s: u32, ) -> Weight;
+	fn create_vs_house() -> Weight;
+	fn fund_house_pot() -> Weight;
+	fn house_move(d: u32, ) -> Weight;
+	fn submit_transcript(m: u32, ) -> Weight;
+	fn replay_transcript(m: u32, ) -> Weight;
+	fn claim_timeout() -> Weight;
+	fn set_session_key() -> Weight;
+	fn remove_session_key() -> Weight;
+	fn prune_game() -> Weight;
+	fn force_end_games(n: u32, ) -> Weight;
+	fn offer_rematch() -> Weight;
+	fn accept_rematch() -> Weight;
+	fn cancel_game() -> Weight;
+	fn set_game_fee() -> Weight;
+	fn set_bet_limits() -> Weight;
 }
 
 /// Weights for pallet_tictactoe using the Substrate node and recommended hardware.
@@ -49,45 +79,99 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Storage: Tictactoe GameIndex (r:1 w:1)
 	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: Tictactoe Games (r:0 w:1)
-	/// Proof: Tictactoe Games (max_values: None, max_size: Some(161), added: 2636, mode: MaxEncodedLen)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameFee (r:1 w:0)
+	/// Proof: Tictactoe GameFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Tictactoe BetLimits (r:1 w:0)
+	/// Proof: Tictactoe BetLimits (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
 	fn create_game() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `6`
 		//  Estimated: `3593`
-		// Minimum execution time: 57_000_000 picoseconds.
-		Weight::from_parts(58_000_000, 3593)
-			.saturating_add(T::DbWeight::get().reads(3_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+		// Minimum execution time: 61_000_000 picoseconds.
+		Weight::from_parts(62_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
 	/// Storage: Tictactoe Games (r:1 w:1)
-	/// Proof: Tictactoe Games (max_values: None, max_size: Some(161), added: 2636, mode: MaxEncodedLen)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
 	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
 	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
-	/// Storage: System Account (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:1 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
 	fn join_game() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `223`
-		//  Estimated: `3626`
-		// Minimum execution time: 52_000_000 picoseconds.
-		Weight::from_parts(54_000_000, 3626)
+		//  Measured:  `326`
+		//  Estimated: `6196`
+		// Minimum execution time: 71_000_000 picoseconds.
+		Weight::from_parts(74_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SessionKeys (r:2 w:0)
+	/// Proof: Tictactoe SessionKeys (max_values: None, max_size: Some(101), added: 2576, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	fn end_game_first_proposal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `476`
+		//  Estimated: `6142`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 6142)
 			.saturating_add(T::DbWeight::get().reads(3_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
 	/// Storage: Tictactoe Games (r:1 w:1)
-	/// Proof: Tictactoe Games (max_values: None, max_size: Some(161), added: 2636, mode: MaxEncodedLen)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SessionKeys (r:2 w:0)
+	/// Proof: Tictactoe SessionKeys (max_values: None, max_size: Some(101), added: 2576, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	fn end_game_mismatch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `537`
+		//  Estimated: `6142`
+		// Minimum execution time: 25_000_000 picoseconds.
+		Weight::from_parts(26_000_000, 6142)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SessionKeys (r:2 w:0)
+	/// Proof: Tictactoe SessionKeys (max_values: None, max_size: Some(101), added: 2576, mode: MaxEncodedLen)
 	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
 	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
-	/// Storage: System Account (r:2 w:2)
+	/// Storage: System Account (r:3 w:3)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
-	fn end_game() -> Weight {
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SideBets (r:1 w:1)
+	/// Proof: Tictactoe SideBets (max_values: None, max_size: Some(3150), added: 5625, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Boards (r:0 w:1)
+	/// Proof: Tictactoe Boards (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	/// Storage: Tictactoe TranscriptHashes (r:1 w:1)
+	/// Proof: Tictactoe TranscriptHashes (max_values: None, max_size: Some(78), added: 2553, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: Tictactoe FeelessCalls (r:0 w:1)
+	/// Proof: Tictactoe FeelessCalls (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	fn end_game_agreement() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `391`
-		//  Estimated: `6196`
-		// Minimum execution time: 54_000_000 picoseconds.
-		Weight::from_parts(56_000_000, 6196)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+		//  Measured:  `722`
+		//  Estimated: `8799`
+		// Minimum execution time: 98_000_000 picoseconds.
+		Weight::from_parts(103_000_000, 8799)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
 	}
 	/// Storage: Tictactoe SafeguardDeposit (r:0 w:1)
 	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
@@ -100,19 +184,70 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
 	/// Storage: Tictactoe Games (r:1 w:1)
-	/// Proof: Tictactoe Games (max_values: None, max_size: Some(161), added: 2636, mode: MaxEncodedLen)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
 	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
 	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
-	/// Storage: System Account (r:2 w:2)
+	/// Storage: System Account (r:67 w:67)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
-	fn force_end_game() -> Weight {
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SideBets (r:1 w:1)
+	/// Proof: Tictactoe SideBets (max_values: None, max_size: Some(3150), added: 5625, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Boards (r:0 w:1)
+	/// Proof: Tictactoe Boards (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	/// Storage: Tictactoe TranscriptHashes (r:1 w:1)
+	/// Proof: Tictactoe TranscriptHashes (max_values: None, max_size: Some(78), added: 2553, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: Tictactoe FeelessCalls (r:0 w:1)
+	/// Proof: Tictactoe FeelessCalls (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 64]`.
+	fn force_end_game(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `462`
-		//  Estimated: `6196`
+		//  Measured:  `638 + n * (81 ±0)`
+		//  Estimated: `11352 + n * (2603 ±0)`
+		// Minimum execution time: 96_000_000 picoseconds.
+		Weight::from_parts(99_000_000, 11352)
+			// Standard Error: 15_000
+			.saturating_add(Weight::from_parts(31_600_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SideBets (r:1 w:1)
+	/// Proof: Tictactoe SideBets (max_values: None, max_size: Some(3150), added: 5625, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe TranscriptHashes (r:1 w:1)
+	/// Proof: Tictactoe TranscriptHashes (max_values: None, max_size: Some(78), added: 2553, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Tournaments (r:1 w:1)
+	/// Proof: Tictactoe Tournaments (max_values: None, max_size: Some(6578), added: 9053, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameIndex (r:1 w:1)
+	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: System Account (r:4 w:4)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Boards (r:0 w:1)
+	/// Proof: Tictactoe Boards (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	/// Storage: Tictactoe FeelessCalls (r:0 w:1)
+	/// Proof: Tictactoe FeelessCalls (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:17)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// The range of component `p` is `[2, 64]`.
+	fn force_end_tournament_game(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `640 + p * (65 ±0)`
+		//  Estimated: `11402`
 		// Minimum execution time: 94_000_000 picoseconds.
-		Weight::from_parts(101_000_000, 6196)
-			.saturating_add(T::DbWeight::get().reads(4_u64))
-			.saturating_add(T::DbWeight::get().writes(3_u64))
+		Weight::from_parts(92_000_000, 11402)
+			// Standard Error: 12_000
+			.saturating_add(Weight::from_parts(1_800_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(13_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into()) / 4))
 	}
 	/// Storage: System Account (r:2 w:2)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
@@ -125,6 +260,358 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(2_u64))
 	}
+	/// Storage: Tictactoe TournamentIndex (r:1 w:1)
+	/// Proof: Tictactoe TournamentIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Tournaments (r:0 w:1)
+	/// Proof: Tictactoe Tournaments (max_values: None, max_size: Some(6578), added: 9053, mode: MaxEncodedLen)
+	fn create_tournament() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1489`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 1489)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tictactoe Tournaments (r:1 w:1)
+	/// Proof: Tictactoe Tournaments (max_values: None, max_size: Some(6578), added: 9053, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameIndex (r:1 w:1)
+	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Games (r:0 w:32)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:32)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// The range of component `p` is `[2, 64]`.
+	fn register_for_tournament(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `190 + p * (32 ±0)`
+		//  Estimated: `10028`
+		// Minimum execution time: 61_000_000 picoseconds.
+		Weight::from_parts(58_000_000, 10028)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(3_400_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(4_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into()) / 2))
+	}
+	/// Storage: Tictactoe Tournaments (r:1 w:1)
+	/// Proof: Tictactoe Tournaments (max_values: None, max_size: Some(6578), added: 9053, mode: MaxEncodedLen)
+	/// Storage: System Account (r:64 w:64)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `p` is `[2, 64]`.
+	fn cancel_tournament(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + p * (32 ±0)`
+		//  Estimated: `10028 + p * (2603 ±0)`
+		// Minimum execution time: 72_000_000 picoseconds.
+		Weight::from_parts(24_000_000, 10028)
+			// Standard Error: 14_000
+			.saturating_add(Weight::from_parts(27_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(p.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Tournaments (r:1 w:1)
+	/// Proof: Tictactoe Tournaments (max_values: None, max_size: Some(6578), added: 9053, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameIndex (r:1 w:1)
+	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: System Account (r:4 w:4)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:16)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// The range of component `p` is `[2, 64]`.
+	fn settle_tournament_game(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612 + p * (65 ±0)`
+		//  Estimated: `11402`
+		// Minimum execution time: 83_000_000 picoseconds.
+		Weight::from_parts(81_000_000, 11402)
+			// Standard Error: 11_000
+			.saturating_add(Weight::from_parts(1_800_000, 0).saturating_mul(p.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(p.into()) / 4))
+	}
+	/// Storage: Tictactoe Games (r:1 w:0)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SideBets (r:1 w:1)
+	/// Proof: Tictactoe SideBets (max_values: None, max_size: Some(3150), added: 5625, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn place_side_bet() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3445`
+		//  Estimated: `6615`
+		// Minimum execution time: 61_000_000 picoseconds.
+		Weight::from_parts(63_000_000, 6615)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tictactoe SideBets (r:1 w:1)
+	/// Proof: Tictactoe SideBets (max_values: None, max_size: Some(3150), added: 5625, mode: MaxEncodedLen)
+	/// Storage: System Account (r:64 w:64)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 64]`.
+	fn settle_side_bets(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `295 + n * (81 ±0)`
+		//  Estimated: `6615 + n * (2603 ±0)`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(29_000_000, 6615)
+			// Standard Error: 14_000
+			.saturating_add(Weight::from_parts(31_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:0)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Boards (r:1 w:1)
+	/// Proof: Tictactoe Boards (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	/// The range of component `s` is `[3, 7]`.
+	fn play_move(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `296 + s * (2 ±0)`
+		//  Estimated: `3626`
+		// Minimum execution time: 29_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 3626)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(6_500_000, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tictactoe HouseBlockExposure (r:1 w:1)
+	/// Proof: Tictactoe HouseBlockExposure (max_values: Some(1), max_size: Some(20), added: 515, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
+	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameIndex (r:1 w:1)
+	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Games (r:0 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameFee (r:1 w:0)
+	/// Proof: Tictactoe GameFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Tictactoe BetLimits (r:1 w:0)
+	/// Proof: Tictactoe BetLimits (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	fn create_vs_house() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `210`
+		//  Estimated: `6196`
+		// Minimum execution time: 98_000_000 picoseconds.
+		Weight::from_parts(101_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn fund_house_pot() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `103`
+		//  Estimated: `3593`
+		// Minimum execution time: 46_000_000 picoseconds.
+		Weight::from_parts(47_000_000, 3593)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `d` is `[0, 4]`.
+	fn house_move(d: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_000_000 picoseconds.
+		Weight::from_parts(0, 0)
+			// Standard Error: 1_900_000
+			.saturating_add(Weight::from_parts(4_600_000, 0).saturating_mul(d.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:0)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe TranscriptHashes (r:1 w:1)
+	/// Proof: Tictactoe TranscriptHashes (max_values: None, max_size: Some(78), added: 2553, mode: MaxEncodedLen)
+	/// The range of component `m` is `[0, 42]`.
+	fn submit_transcript(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `318`
+		//  Estimated: `3626`
+		// Minimum execution time: 27_000_000 picoseconds.
+		Weight::from_parts(28_000_000, 3626)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(95_000, 0).saturating_mul(m.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// The range of component `m` is `[0, 42]`.
+	fn replay_transcript(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_000_000 picoseconds.
+		Weight::from_parts(1_000_000, 0)
+			// Standard Error: 11_000
+			.saturating_add(Weight::from_parts(2_100_000, 0).saturating_mul(m.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:0)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:1 w:0)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Boards (r:1 w:0)
+	/// Proof: Tictactoe Boards (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	fn claim_timeout() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402`
+		//  Estimated: `3679`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 3679)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+	}
+	/// Storage: Tictactoe SessionKeys (r:0 w:1)
+	/// Proof: Tictactoe SessionKeys (max_values: None, max_size: Some(101), added: 2576, mode: MaxEncodedLen)
+	fn set_session_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `0`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tictactoe SessionKeys (r:1 w:1)
+	/// Proof: Tictactoe SessionKeys (max_values: None, max_size: Some(101), added: 2576, mode: MaxEncodedLen)
+	fn remove_session_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `135`
+		//  Estimated: `3566`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 3566)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn prune_game() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `391`
+		//  Estimated: `6196`
+		// Minimum execution time: 47_000_000 picoseconds.
+		Weight::from_parts(49_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(3_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
+	/// Storage: Tictactoe Games (r:64 w:64)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
+	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:128 w:128)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 64]`.
+	fn force_end_games(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `336 + n * (245 ±0)`
+		//  Estimated: `3593 + n * (5206 ±0)`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 3593)
+			// Standard Error: 48_000
+			.saturating_add(Weight::from_parts(96_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5206).saturating_mul(n.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:0)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe RematchOffers (r:1 w:1)
+	/// Proof: Tictactoe RematchOffers (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	fn offer_rematch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `391`
+		//  Estimated: `3679`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 3679)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe RematchOffers (r:1 w:1)
+	/// Proof: Tictactoe RematchOffers (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
+	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameIndex (r:1 w:1)
+	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameFee (r:1 w:0)
+	/// Proof: Tictactoe GameFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Tictactoe BetLimits (r:1 w:0)
+	/// Proof: Tictactoe BetLimits (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	fn accept_rematch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `8799`
+		// Minimum execution time: 106_000_000 picoseconds.
+		Weight::from_parts(109_000_000, 8799)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
+	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	fn cancel_game() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `362`
+		//  Estimated: `6196`
+		// Minimum execution time: 63_000_000 picoseconds.
+		Weight::from_parts(65_000_000, 6196)
+			.saturating_add(T::DbWeight::get().reads(5_u64))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+	}
+	/// Storage: Tictactoe GameFee (r:0 w:1)
+	/// Proof: Tictactoe GameFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn set_game_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tictactoe BetLimits (r:0 w:1)
+	/// Proof: Tictactoe BetLimits (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	fn set_bet_limits() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -136,45 +623,99 @@ impl WeightInfo for () {
 	/// Storage: Tictactoe GameIndex (r:1 w:1)
 	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: Tictactoe Games (r:0 w:1)
-	/// Proof: Tictactoe Games (max_values: None, max_size: Some(161), added: 2636, mode: MaxEncodedLen)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameFee (r:1 w:0)
+	/// Proof: Tictactoe GameFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Tictactoe BetLimits (r:1 w:0)
+	/// Proof: Tictactoe BetLimits (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
 	fn create_game() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `6`
 		//  Estimated: `3593`
-		// Minimum execution time: 57_000_000 picoseconds.
-		Weight::from_parts(58_000_000, 3593)
-			.saturating_add(RocksDbWeight::get().reads(3_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+		// Minimum execution time: 61_000_000 picoseconds.
+		Weight::from_parts(62_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
 	/// Storage: Tictactoe Games (r:1 w:1)
-	/// Proof: Tictactoe Games (max_values: None, max_size: Some(161), added: 2636, mode: MaxEncodedLen)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
 	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
 	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
-	/// Storage: System Account (r:1 w:1)
+	/// Storage: System Account (r:2 w:2)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:1 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
 	fn join_game() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `223`
-		//  Estimated: `3626`
-		// Minimum execution time: 52_000_000 picoseconds.
-		Weight::from_parts(54_000_000, 3626)
+		//  Measured:  `326`
+		//  Estimated: `6196`
+		// Minimum execution time: 71_000_000 picoseconds.
+		Weight::from_parts(74_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SessionKeys (r:2 w:0)
+	/// Proof: Tictactoe SessionKeys (max_values: None, max_size: Some(101), added: 2576, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	fn end_game_first_proposal() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `476`
+		//  Estimated: `6142`
+		// Minimum execution time: 24_000_000 picoseconds.
+		Weight::from_parts(25_000_000, 6142)
 			.saturating_add(RocksDbWeight::get().reads(3_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
 	/// Storage: Tictactoe Games (r:1 w:1)
-	/// Proof: Tictactoe Games (max_values: None, max_size: Some(161), added: 2636, mode: MaxEncodedLen)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SessionKeys (r:2 w:0)
+	/// Proof: Tictactoe SessionKeys (max_values: None, max_size: Some(101), added: 2576, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	fn end_game_mismatch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `537`
+		//  Estimated: `6142`
+		// Minimum execution time: 25_000_000 picoseconds.
+		Weight::from_parts(26_000_000, 6142)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SessionKeys (r:2 w:0)
+	/// Proof: Tictactoe SessionKeys (max_values: None, max_size: Some(101), added: 2576, mode: MaxEncodedLen)
 	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
 	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
-	/// Storage: System Account (r:2 w:2)
+	/// Storage: System Account (r:3 w:3)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
-	fn end_game() -> Weight {
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SideBets (r:1 w:1)
+	/// Proof: Tictactoe SideBets (max_values: None, max_size: Some(3150), added: 5625, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Boards (r:0 w:1)
+	/// Proof: Tictactoe Boards (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	/// Storage: Tictactoe TranscriptHashes (r:1 w:1)
+	/// Proof: Tictactoe TranscriptHashes (max_values: None, max_size: Some(78), added: 2553, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: Tictactoe FeelessCalls (r:0 w:1)
+	/// Proof: Tictactoe FeelessCalls (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	fn end_game_agreement() -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `391`
-		//  Estimated: `6196`
-		// Minimum execution time: 54_000_000 picoseconds.
-		Weight::from_parts(56_000_000, 6196)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+		//  Measured:  `722`
+		//  Estimated: `8799`
+		// Minimum execution time: 98_000_000 picoseconds.
+		Weight::from_parts(103_000_000, 8799)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
 	}
 	/// Storage: Tictactoe SafeguardDeposit (r:0 w:1)
 	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
@@ -187,19 +728,70 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
 	/// Storage: Tictactoe Games (r:1 w:1)
-	/// Proof: Tictactoe Games (max_values: None, max_size: Some(161), added: 2636, mode: MaxEncodedLen)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
 	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
 	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
-	/// Storage: System Account (r:2 w:2)
+	/// Storage: System Account (r:67 w:67)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
-	fn force_end_game() -> Weight {
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SideBets (r:1 w:1)
+	/// Proof: Tictactoe SideBets (max_values: None, max_size: Some(3150), added: 5625, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Boards (r:0 w:1)
+	/// Proof: Tictactoe Boards (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	/// Storage: Tictactoe TranscriptHashes (r:1 w:1)
+	/// Proof: Tictactoe TranscriptHashes (max_values: None, max_size: Some(78), added: 2553, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: Tictactoe FeelessCalls (r:0 w:1)
+	/// Proof: Tictactoe FeelessCalls (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 64]`.
+	fn force_end_game(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
-		//  Measured:  `462`
-		//  Estimated: `6196`
+		//  Measured:  `638 + n * (81 ±0)`
+		//  Estimated: `11352 + n * (2603 ±0)`
+		// Minimum execution time: 96_000_000 picoseconds.
+		Weight::from_parts(99_000_000, 11352)
+			// Standard Error: 15_000
+			.saturating_add(Weight::from_parts(31_600_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SideBets (r:1 w:1)
+	/// Proof: Tictactoe SideBets (max_values: None, max_size: Some(3150), added: 5625, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe TranscriptHashes (r:1 w:1)
+	/// Proof: Tictactoe TranscriptHashes (max_values: None, max_size: Some(78), added: 2553, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Tournaments (r:1 w:1)
+	/// Proof: Tictactoe Tournaments (max_values: None, max_size: Some(6578), added: 9053, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameIndex (r:1 w:1)
+	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: System Account (r:4 w:4)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Boards (r:0 w:1)
+	/// Proof: Tictactoe Boards (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	/// Storage: Tictactoe FeelessCalls (r:0 w:1)
+	/// Proof: Tictactoe FeelessCalls (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:17)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// The range of component `p` is `[2, 64]`.
+	fn force_end_tournament_game(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `640 + p * (65 ±0)`
+		//  Estimated: `11402`
 		// Minimum execution time: 94_000_000 picoseconds.
-		Weight::from_parts(101_000_000, 6196)
-			.saturating_add(RocksDbWeight::get().reads(4_u64))
-			.saturating_add(RocksDbWeight::get().writes(3_u64))
+		Weight::from_parts(92_000_000, 11402)
+			// Standard Error: 12_000
+			.saturating_add(Weight::from_parts(1_800_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(13_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into()) / 4))
 	}
 	/// Storage: System Account (r:2 w:2)
 	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
@@ -212,4 +804,356 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(2_u64))
 	}
+	/// Storage: Tictactoe TournamentIndex (r:1 w:1)
+	/// Proof: Tictactoe TournamentIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Tournaments (r:0 w:1)
+	/// Proof: Tictactoe Tournaments (max_values: None, max_size: Some(6578), added: 9053, mode: MaxEncodedLen)
+	fn create_tournament() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `1489`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 1489)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tictactoe Tournaments (r:1 w:1)
+	/// Proof: Tictactoe Tournaments (max_values: None, max_size: Some(6578), added: 9053, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameIndex (r:1 w:1)
+	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Games (r:0 w:32)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:32)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// The range of component `p` is `[2, 64]`.
+	fn register_for_tournament(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `190 + p * (32 ±0)`
+		//  Estimated: `10028`
+		// Minimum execution time: 61_000_000 picoseconds.
+		Weight::from_parts(58_000_000, 10028)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(3_400_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(4_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into()) / 2))
+	}
+	/// Storage: Tictactoe Tournaments (r:1 w:1)
+	/// Proof: Tictactoe Tournaments (max_values: None, max_size: Some(6578), added: 9053, mode: MaxEncodedLen)
+	/// Storage: System Account (r:64 w:64)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `p` is `[2, 64]`.
+	fn cancel_tournament(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `264 + p * (32 ±0)`
+		//  Estimated: `10028 + p * (2603 ±0)`
+		// Minimum execution time: 72_000_000 picoseconds.
+		Weight::from_parts(24_000_000, 10028)
+			// Standard Error: 14_000
+			.saturating_add(Weight::from_parts(27_000_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(p.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(p.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(p.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Tournaments (r:1 w:1)
+	/// Proof: Tictactoe Tournaments (max_values: None, max_size: Some(6578), added: 9053, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameIndex (r:1 w:1)
+	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: System Account (r:4 w:4)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:16)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// The range of component `p` is `[2, 64]`.
+	fn settle_tournament_game(p: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612 + p * (65 ±0)`
+		//  Estimated: `11402`
+		// Minimum execution time: 83_000_000 picoseconds.
+		Weight::from_parts(81_000_000, 11402)
+			// Standard Error: 11_000
+			.saturating_add(Weight::from_parts(1_800_000, 0).saturating_mul(p.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(p.into()) / 4))
+	}
+	/// Storage: Tictactoe Games (r:1 w:0)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SideBets (r:1 w:1)
+	/// Proof: Tictactoe SideBets (max_values: None, max_size: Some(3150), added: 5625, mode: MaxEncodedLen)
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn place_side_bet() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3445`
+		//  Estimated: `6615`
+		// Minimum execution time: 61_000_000 picoseconds.
+		Weight::from_parts(63_000_000, 6615)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Tictactoe SideBets (r:1 w:1)
+	/// Proof: Tictactoe SideBets (max_values: None, max_size: Some(3150), added: 5625, mode: MaxEncodedLen)
+	/// Storage: System Account (r:64 w:64)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 64]`.
+	fn settle_side_bets(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `295 + n * (81 ±0)`
+		//  Estimated: `6615 + n * (2603 ±0)`
+		// Minimum execution time: 41_000_000 picoseconds.
+		Weight::from_parts(29_000_000, 6615)
+			// Standard Error: 14_000
+			.saturating_add(Weight::from_parts(31_500_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(n.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:0)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Boards (r:1 w:1)
+	/// Proof: Tictactoe Boards (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	/// The range of component `s` is `[3, 7]`.
+	fn play_move(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `296 + s * (2 ±0)`
+		//  Estimated: `3626`
+		// Minimum execution time: 29_000_000 picoseconds.
+		Weight::from_parts(9_000_000, 3626)
+			// Standard Error: 9_000
+			.saturating_add(Weight::from_parts(6_500_000, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tictactoe HouseBlockExposure (r:1 w:1)
+	/// Proof: Tictactoe HouseBlockExposure (max_values: Some(1), max_size: Some(20), added: 515, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
+	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameIndex (r:1 w:1)
+	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Games (r:0 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameFee (r:1 w:0)
+	/// Proof: Tictactoe GameFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Tictactoe BetLimits (r:1 w:0)
+	/// Proof: Tictactoe BetLimits (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	fn create_vs_house() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `210`
+		//  Estimated: `6196`
+		// Minimum execution time: 98_000_000 picoseconds.
+		Weight::from_parts(101_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: System Account (r:1 w:1)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn fund_house_pot() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `103`
+		//  Estimated: `3593`
+		// Minimum execution time: 46_000_000 picoseconds.
+		Weight::from_parts(47_000_000, 3593)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `d` is `[0, 4]`.
+	fn house_move(d: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 2_000_000 picoseconds.
+		Weight::from_parts(0, 0)
+			// Standard Error: 1_900_000
+			.saturating_add(Weight::from_parts(4_600_000, 0).saturating_mul(d.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:0)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe TranscriptHashes (r:1 w:1)
+	/// Proof: Tictactoe TranscriptHashes (max_values: None, max_size: Some(78), added: 2553, mode: MaxEncodedLen)
+	/// The range of component `m` is `[0, 42]`.
+	fn submit_transcript(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `318`
+		//  Estimated: `3626`
+		// Minimum execution time: 27_000_000 picoseconds.
+		Weight::from_parts(28_000_000, 3626)
+			// Standard Error: 2_000
+			.saturating_add(Weight::from_parts(95_000, 0).saturating_mul(m.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// The range of component `m` is `[0, 42]`.
+	fn replay_transcript(m: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 1_000_000 picoseconds.
+		Weight::from_parts(1_000_000, 0)
+			// Standard Error: 11_000
+			.saturating_add(Weight::from_parts(2_100_000, 0).saturating_mul(m.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:0)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:1 w:0)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	/// Storage: Tictactoe Boards (r:1 w:0)
+	/// Proof: Tictactoe Boards (max_values: None, max_size: Some(113), added: 2588, mode: MaxEncodedLen)
+	fn claim_timeout() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `402`
+		//  Estimated: `3679`
+		// Minimum execution time: 21_000_000 picoseconds.
+		Weight::from_parts(22_000_000, 3679)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+	}
+	/// Storage: Tictactoe SessionKeys (r:0 w:1)
+	/// Proof: Tictactoe SessionKeys (max_values: None, max_size: Some(101), added: 2576, mode: MaxEncodedLen)
+	fn set_session_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6`
+		//  Estimated: `0`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tictactoe SessionKeys (r:1 w:1)
+	/// Proof: Tictactoe SessionKeys (max_values: None, max_size: Some(101), added: 2576, mode: MaxEncodedLen)
+	fn remove_session_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `135`
+		//  Estimated: `3566`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 3566)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	fn prune_game() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `391`
+		//  Estimated: `6196`
+		// Minimum execution time: 47_000_000 picoseconds.
+		Weight::from_parts(49_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(3_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
+	/// Storage: Tictactoe Games (r:64 w:64)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
+	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:128 w:128)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// The range of component `n` is `[1, 64]`.
+	fn force_end_games(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `336 + n * (245 ±0)`
+		//  Estimated: `3593 + n * (5206 ±0)`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 3593)
+			// Standard Error: 48_000
+			.saturating_add(Weight::from_parts(96_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 5206).saturating_mul(n.into()))
+	}
+	/// Storage: Tictactoe Games (r:1 w:0)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe RematchOffers (r:1 w:1)
+	/// Proof: Tictactoe RematchOffers (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	fn offer_rematch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `391`
+		//  Estimated: `3679`
+		// Minimum execution time: 18_000_000 picoseconds.
+		Weight::from_parts(19_000_000, 3679)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe RematchOffers (r:1 w:1)
+	/// Proof: Tictactoe RematchOffers (max_values: None, max_size: Some(56), added: 2531, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
+	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:3 w:3)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameIndex (r:1 w:1)
+	/// Proof: Tictactoe GameIndex (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe GameFee (r:1 w:0)
+	/// Proof: Tictactoe GameFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: Tictactoe BetLimits (r:1 w:0)
+	/// Proof: Tictactoe BetLimits (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	fn accept_rematch() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `8799`
+		// Minimum execution time: 106_000_000 picoseconds.
+		Weight::from_parts(109_000_000, 8799)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Tictactoe Games (r:1 w:1)
+	/// Proof: Tictactoe Games (max_values: None, max_size: Some(214), added: 2689, mode: MaxEncodedLen)
+	/// Storage: Tictactoe OpenGames (r:1 w:1)
+	/// Proof: Tictactoe OpenGames (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Tictactoe SafeguardDeposit (r:1 w:0)
+	/// Proof: Tictactoe SafeguardDeposit (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	/// Storage: System Account (r:2 w:2)
+	/// Proof: System Account (max_values: None, max_size: Some(128), added: 2603, mode: MaxEncodedLen)
+	/// Storage: Tictactoe MoveDeadlines (r:0 w:1)
+	/// Proof: Tictactoe MoveDeadlines (max_values: None, max_size: Some(16), added: 2491, mode: MaxEncodedLen)
+	fn cancel_game() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `362`
+		//  Estimated: `6196`
+		// Minimum execution time: 63_000_000 picoseconds.
+		Weight::from_parts(65_000_000, 6196)
+			.saturating_add(RocksDbWeight::get().reads(5_u64))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+	}
+	/// Storage: Tictactoe GameFee (r:0 w:1)
+	/// Proof: Tictactoe GameFee (max_values: Some(1), max_size: Some(16), added: 511, mode: MaxEncodedLen)
+	fn set_game_fee() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Tictactoe BetLimits (r:0 w:1)
+	/// Proof: Tictactoe BetLimits (max_values: Some(1), max_size: Some(33), added: 528, mode: MaxEncodedLen)
+	fn set_bet_limits() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 7_000_000 picoseconds.
+		Weight::from_parts(8_000_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
 }
@@ -6,6 +6,7 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::Encode;
 use pallet_grandpa::AuthorityId as GrandpaId;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -15,8 +16,8 @@ use sp_runtime::{
 	traits::{
 		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+	ApplyExtrinsicResult, MultiSignature, SaturatedConversion,
 };
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
@@ -39,6 +40,7 @@ pub use frame_support::{
 };
 pub use frame_system::Call as SystemCall;
 pub use pallet_balances::Call as BalancesCall;
+pub use pallet_tictactoe::Call as TictactoeCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
 #[cfg(any(feature = "std", test))]
@@ -48,6 +50,9 @@ pub use sp_runtime::{Perbill, Permill};
 /// Import the template pallet.
 pub use pallet_template;
 
+/// Import the tictactoe pallet.
+pub use pallet_tictactoe;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -275,6 +280,8 @@ impl pallet_template::Config for Runtime {
 
 parameter_types! {
 	pub const TictactoePalletId: PalletId = PalletId(*b"py/tctct");
+	pub const TictactoeMaxBoardSize: u32 = 7;
+	pub const TictactoeUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 }
 
 /// Configure the pallet-tictactoe in pallets/tictactoe.
@@ -283,6 +290,75 @@ impl pallet_tictactoe::Config for Runtime {
 	type PalletId = TictactoePalletId;
 	type Currency = Balances;
 	type WeightInfo = pallet_tictactoe::weights::SubstrateWeight<Runtime>;
+	type MaxTournamentPlayers = ConstU32<64>;
+	type MaxPrizePositions = ConstU32<8>;
+	type MaxSeriesLength = ConstU32<7>;
+	type MaxSideBetsPerGame = ConstU32<64>;
+	type MaxBoardSize = TictactoeMaxBoardSize;
+	type Rules = pallet_tictactoe::KInARow<TictactoeMaxBoardSize>;
+	type MaxHouseBet = ConstU128<1_000_000_000_000>;
+	type MaxHouseExposurePerBlock = ConstU128<10_000_000_000_000>;
+	type MaxHouseDifficulty = ConstU8<4>;
+	type AuthorityId = pallet_tictactoe::crypto::TictactoeAuthId;
+	type MoveTimeout = ConstU32<100>;
+	type OpenGameTimeout = ConstU32<DAYS>;
+	type UnsignedPriority = TictactoeUnsignedPriority;
+	type GameDepositPerByte = ConstU128<10_000>;
+	type MaxOpenGames = ConstU32<10_000>;
+	type MaxForceEndBatch = ConstU32<64>;
+	type RematchOfferTimeout = ConstU32<100>;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(
+		RuntimeCall,
+		<UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::SignaturePayload,
+	)> {
+		let tip = 0;
+		// Transactions of the offchain worker are valid for half the block hash count.
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let era = generic::Era::mortal(period, current_block);
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(era),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_tictactoe::CheckTictactoeMove::<Runtime, _>::from(
+				pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+			),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let address = sp_runtime::MultiAddress::Id(account);
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (address, signature, extra)))
+	}
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = UncheckedExtrinsic;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -321,7 +397,11 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	// Moves and settlement calls of the players of a game don't pay fees.
+	pallet_tictactoe::CheckTictactoeMove<
+		Runtime,
+		pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -522,6 +602,16 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_tictactoe_runtime_api::TictactoeApi<Block, AccountId, Balance> for Runtime {
+		fn simulate_end_game(
+			game_index: u32,
+			caller: AccountId,
+			winner: AccountId,
+		) -> pallet_tictactoe_runtime_api::EndGameOutcome<AccountId, Balance> {
+			Tictactoe::simulate_end_game(game_index, caller, winner)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (